@@ -0,0 +1,15 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use embedded_pcf::load_pcf_font;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(font) = load_pcf_font(Cursor::new(data)) else {
+        return;
+    };
+    let _ = font.properties().count();
+    let _ = font.font_info();
+    let _ = font.glyph_index_by_name(".notdef");
+});
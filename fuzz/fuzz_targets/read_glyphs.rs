@@ -0,0 +1,23 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use embedded_pcf::load_pcf_font;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(font) = load_pcf_font(Cursor::new(data)) else {
+        return;
+    };
+    let mut buffer = [0; 512];
+    let glyph_count = font.glyph_count().min(64) as u16;
+    for glyph_index in 0..glyph_count {
+        let _ = font.read_glyph_raw_by_index(glyph_index, &mut buffer);
+        let _ = font.glyph_name(glyph_index, &mut buffer);
+    }
+    for code_point in (0..=0x1_0000).step_by(97) {
+        let _ = font.read_glyph_raw(code_point, &mut buffer);
+        let _ = font.get_glyph_ink_metrics(code_point);
+        let _ = font.scalable_width(code_point);
+    }
+});
@@ -0,0 +1,106 @@
+#!/usr/bin/env python3
+"""Generate the charset mapping tables in `src/charset/` from the Python codecs.
+
+Run from the repository root: `python3 scripts/charset_tables.py`.
+"""
+
+from pathlib import Path
+
+OUTPUT = Path("src/charset")
+HEADER = "//! {} mapping, generated by `scripts/charset_tables.py`, don't edit.\n"
+PER_LINE = 12
+
+
+def array(name, doc, values):
+    lines = [f"/// {doc}", "#[rustfmt::skip]", f"pub(super) static {name}: [u16; {len(values)}] = ["]
+    for start in range(0, len(values), PER_LINE):
+        chunk = values[start : start + PER_LINE]
+        lines.append("    " + " ".join(f"0x{value:04X}," for value in chunk))
+    lines.append("];")
+    return "\n".join(lines) + "\n"
+
+
+def decode(codec, data):
+    try:
+        text = data.decode(codec)
+    except UnicodeDecodeError:
+        return None
+    if len(text) != 1 or ord(text) > 0xFFFF:
+        return None
+    return ord(text)
+
+
+def single_byte(codec, first):
+    """Unicode of each byte from `first` to 0xFF, 0 if unmapped."""
+    return [decode(codec, bytes([byte])) or 0 for byte in range(first, 0x100)]
+
+
+def double_byte(codec, leads, trails, native):
+    """`(unicode, native)` pairs sorted by unicode, keeping the first native code of duplicates."""
+    pairs = {}
+    for lead in leads:
+        for trail in trails:
+            unicode = decode(codec, bytes([lead, trail]))
+            if unicode is not None and unicode >= 0x80:
+                pairs.setdefault(unicode, native(lead, trail))
+    return sorted(pairs.items())
+
+
+def write_double_byte(file, name, native_doc, pairs):
+    text = HEADER.format(f"{name} to Unicode") + "\n"
+    text += array("UNICODE", "Unicode code points, sorted", [unicode for unicode, _ in pairs])
+    text += "\n" + array("NATIVE", native_doc, [native for _, native in pairs])
+    (OUTPUT / file).write_text(text)
+
+
+def main():
+    parts = [part for part in range(2, 17) if part != 12]
+    text = HEADER.format("ISO8859 to Unicode") + "\n"
+    for part in parts:
+        text += array(
+            f"ISO8859_{part}",
+            f"Unicode of ISO8859-{part} `0xA0..=0xFF`, 0 if unmapped",
+            single_byte(f"iso8859_{part}", 0xA0),
+        )
+        text += "\n"
+    text += "/// The table of the ISO8859 part, `0xA0..=0xFF`.\n"
+    text += "pub(super) fn table(part: u8) -> Option<&'static [u16; 96]> {\n"
+    text += "    let table = match part {\n"
+    for part in parts:
+        text += f"        {part} => &ISO8859_{part},\n"
+    text += "        _ => return None,\n    };\n    Some(table)\n}\n"
+    (OUTPUT / "iso8859.rs").write_text(text)
+
+    text = HEADER.format("KOI8-R to Unicode") + "\n"
+    text += array("KOI8_R", "Unicode of KOI8-R `0x80..=0xFF`", single_byte("koi8_r", 0x80))
+    (OUTPUT / "koi8_r.rs").write_text(text)
+
+    gl = lambda lead, trail: ((lead - 0x80) << 8) | (trail - 0x80)
+    high = range(0xA1, 0xFF)
+    write_double_byte(
+        "jisx0208.rs",
+        "JIS X 0208",
+        "JIS X 0208 code points at the same positions, in the GL form used by fonts(`0x2121..`)",
+        double_byte("euc_jp", high, high, gl),
+    )
+    write_double_byte(
+        "gb2312.rs",
+        "GB2312",
+        "GB2312 code points at the same positions, in the GL form used by fonts(`0x2121..`)",
+        double_byte("gb2312", high, high, gl),
+    )
+    write_double_byte(
+        "big5.rs",
+        "Big5",
+        "Big5 code points at the same positions",
+        double_byte(
+            "big5",
+            range(0xA1, 0xFA),
+            list(range(0x40, 0x7F)) + list(range(0xA1, 0xFF)),
+            lambda lead, trail: (lead << 8) | trail,
+        ),
+    )
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,243 @@
+//! Font charsets, translating Unicode into the encoding of the font.
+//!
+//! The charset comes from the `CHARSET_REGISTRY` and `CHARSET_ENCODING` properties. Mapping tables
+//! of the legacy charsets are enabled by the `charset-*` features, without them only the code
+//! points shared with Unicode(e.g. ASCII in `ISO8859-2`) are mapped, the others are missing.
+//! Use [Charset::Unknown] to look up glyphs with the font's own code points instead.
+
+#[cfg(feature = "charset-big5")]
+mod big5;
+#[cfg(feature = "charset-gb2312")]
+mod gb2312;
+#[cfg(feature = "charset-iso8859")]
+mod iso8859;
+#[cfg(feature = "charset-jisx0208")]
+mod jisx0208;
+#[cfg(feature = "charset-koi8-r")]
+mod koi8_r;
+
+/// Encoding of the font's code points, see [crate::PcfFont::charset].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Charset {
+    /// `ISO10646-1`
+    Unicode,
+    /// `ISO8859-<part>`, `ISO8859-1` is the first 256 code points of Unicode
+    ///
+    /// Needs the `charset-iso8859` feature, except for `ISO8859-1`.
+    Iso8859(u8),
+    /// `KOI8-R`, needs the `charset-koi8-r` feature
+    Koi8R,
+    /// `JISX0208.1983-0` and the other editions, needs the `charset-jisx0208` feature
+    JisX0208,
+    /// `GB2312.1980-0`, needs the `charset-gb2312` feature
+    Gb2312,
+    /// `BIG5-0` or `BIG5.ETEN-0`, needs the `charset-big5` feature
+    Big5,
+    /// Any other charset, code points are used as they are
+    Unknown,
+}
+
+impl Charset {
+    /// Detect the charset from the `CHARSET_REGISTRY` & `CHARSET_ENCODING` values, case
+    /// insensitive.
+    pub fn from_registry(registry: &[u8], encoding: &[u8]) -> Self {
+        let starts_with = |prefix: &[u8]| {
+            registry
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        };
+        if registry.eq_ignore_ascii_case(b"ISO10646") {
+            Self::Unicode
+        } else if registry.eq_ignore_ascii_case(b"ISO8859") {
+            let part = core::str::from_utf8(encoding)
+                .ok()
+                .and_then(|s| s.parse().ok());
+            match part {
+                // there's no part 12
+                Some(part @ (1..=11 | 13..=16)) => Self::Iso8859(part),
+                _ => Self::Unknown,
+            }
+        } else if registry.eq_ignore_ascii_case(b"KOI8") && encoding.eq_ignore_ascii_case(b"R") {
+            Self::Koi8R
+        } else if starts_with(b"JISX0208") {
+            Self::JisX0208
+        } else if starts_with(b"GB2312") {
+            Self::Gb2312
+        } else if starts_with(b"BIG5") {
+            Self::Big5
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Translate the Unicode code point into the font's encoding, `None` if it can't be encoded.
+    pub fn encode(self, code_point: u32) -> Option<u32> {
+        match self {
+            Self::Unicode | Self::Unknown => return Some(code_point),
+            Self::Iso8859(1) => return (code_point <= 0xFF).then_some(code_point),
+            _ if code_point < self.shared_range() => return Some(code_point),
+            _ => {}
+        }
+        let code_point = u16::try_from(code_point).ok()?;
+        if let Some((first, table)) = self.single_byte_table() {
+            let index = table.iter().position(|unicode| *unicode == code_point)?;
+            return Some(first + index as u32);
+        }
+        let (unicode, native) = self.double_byte_tables()?;
+        let index = unicode.binary_search(&code_point).ok()?;
+        Some(native[index] as u32)
+    }
+
+    /// Translate the code point in the font's encoding into Unicode, `None` if it's unknown.
+    pub fn decode(self, code_point: u32) -> Option<u32> {
+        match self {
+            Self::Unicode | Self::Unknown => return Some(code_point),
+            Self::Iso8859(1) => return (code_point <= 0xFF).then_some(code_point),
+            _ if code_point < self.shared_range() => return Some(code_point),
+            _ => {}
+        }
+        if let Some((first, table)) = self.single_byte_table() {
+            let unicode = *table.get(code_point.checked_sub(first)? as usize)?;
+            return (unicode != 0).then_some(unicode as u32);
+        }
+        let (unicode, native) = self.double_byte_tables()?;
+        let index = native
+            .iter()
+            .position(|native| *native as u32 == code_point)?;
+        Some(unicode[index] as u32)
+    }
+
+    /// Code points below this are the same with Unicode.
+    fn shared_range(self) -> u32 {
+        match self {
+            // C1 controls are kept as well
+            Self::Iso8859(_) => 0xA0,
+            Self::Koi8R => 0x80,
+            _ => 0,
+        }
+    }
+
+    /// The first code point and the Unicode of the rest up to 0xFF, 0 if unmapped.
+    fn single_byte_table(self) -> Option<(u32, &'static [u16])> {
+        match self {
+            #[cfg(feature = "charset-iso8859")]
+            Self::Iso8859(part) => Some((0xA0, iso8859::table(part)?)),
+            #[cfg(feature = "charset-koi8-r")]
+            Self::Koi8R => Some((0x80, &koi8_r::KOI8_R)),
+            _ => None,
+        }
+    }
+
+    /// `(Unicode, native)` code points of the double byte charsets, sorted by Unicode.
+    fn double_byte_tables(self) -> Option<(&'static [u16], &'static [u16])> {
+        match self {
+            #[cfg(feature = "charset-jisx0208")]
+            Self::JisX0208 => Some((&jisx0208::UNICODE, &jisx0208::NATIVE)),
+            #[cfg(feature = "charset-gb2312")]
+            Self::Gb2312 => Some((&gb2312::UNICODE, &gb2312::NATIVE)),
+            #[cfg(feature = "charset-big5")]
+            Self::Big5 => Some((&big5::UNICODE, &big5::NATIVE)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detecting_charsets() {
+        for (registry, encoding, charset) in [
+            ("ISO10646", "1", Charset::Unicode),
+            ("iso8859", "1", Charset::Iso8859(1)),
+            ("ISO8859", "15", Charset::Iso8859(15)),
+            ("ISO8859", "12", Charset::Unknown),
+            ("ISO8859", "17", Charset::Unknown),
+            ("KOI8", "R", Charset::Koi8R),
+            ("KOI8", "U", Charset::Unknown),
+            ("JISX0208.1983", "0", Charset::JisX0208),
+            ("GB2312.1980", "0", Charset::Gb2312),
+            ("Big5.ETen", "0", Charset::Big5),
+            ("misc", "fontspecific", Charset::Unknown),
+            ("", "", Charset::Unknown),
+        ] {
+            assert_eq!(
+                Charset::from_registry(registry.as_bytes(), encoding.as_bytes()),
+                charset,
+                "{registry}-{encoding}"
+            );
+        }
+    }
+
+    #[test]
+    fn encoding_without_tables() {
+        assert_eq!(Charset::Unicode.encode(0x1F600), Some(0x1F600));
+        assert_eq!(Charset::Iso8859(1).encode('Ä' as u32), Some(0xC4));
+        assert_eq!(Charset::Iso8859(1).encode('Ő' as u32), None);
+        assert_eq!(Charset::Iso8859(1).decode(0xC4), Some('Ä' as u32));
+        // ASCII is shared
+        assert_eq!(Charset::Iso8859(5).encode('A' as u32), Some(0x41));
+        assert_eq!(Charset::Koi8R.encode('A' as u32), Some(0x41));
+        assert_eq!(Charset::Koi8R.decode(0x41), Some('A' as u32));
+    }
+
+    #[test]
+    #[cfg(not(feature = "charset-gb2312"))]
+    fn encoding_without_gb2312_table() {
+        // nothing is shared with Unicode, the native code points are never guessed
+        assert_eq!(Charset::Gb2312.encode('A' as u32), None);
+        assert_eq!(Charset::Gb2312.encode(0x3021), None);
+        assert_eq!(Charset::Gb2312.decode(0x3021), None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "charset-koi8-r"))]
+    fn encoding_without_koi8_r_table() {
+        assert_eq!(Charset::Koi8R.encode('A' as u32), Some(0x41));
+        assert_eq!(Charset::Koi8R.encode('Ж' as u32), None);
+        assert_eq!(Charset::Koi8R.decode(0xF6), None);
+    }
+
+    #[test]
+    #[cfg(feature = "charset-iso8859")]
+    fn encoding_iso8859() {
+        assert_eq!(Charset::Iso8859(2).encode('Ő' as u32), Some(0xD5));
+        assert_eq!(Charset::Iso8859(2).decode(0xD5), Some('Ő' as u32));
+        assert_eq!(Charset::Iso8859(5).encode('Ж' as u32), Some(0xB6));
+        assert_eq!(Charset::Iso8859(15).encode('€' as u32), Some(0xA4));
+        assert_eq!(Charset::Iso8859(15).encode('¤' as u32), None);
+        assert_eq!(Charset::Iso8859(2).encode(0x85), Some(0x85));
+    }
+
+    #[test]
+    #[cfg(feature = "charset-koi8-r")]
+    fn encoding_koi8_r() {
+        assert_eq!(Charset::Koi8R.encode('Ж' as u32), Some(0xF6));
+        assert_eq!(Charset::Koi8R.decode(0xF6), Some('Ж' as u32));
+        assert_eq!(Charset::Koi8R.encode('Ä' as u32), None);
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "charset-jisx0208",
+        feature = "charset-gb2312",
+        feature = "charset-big5"
+    ))]
+    fn encoding_double_byte_charsets() {
+        assert_eq!(Charset::JisX0208.encode('亜' as u32), Some(0x3021));
+        assert_eq!(Charset::JisX0208.encode('あ' as u32), Some(0x2422));
+        assert_eq!(Charset::JisX0208.decode(0x2422), Some('あ' as u32));
+        assert_eq!(Charset::Gb2312.encode('啊' as u32), Some(0x3021));
+        assert_eq!(Charset::Gb2312.encode('世' as u32), Some(0x4A40));
+        assert_eq!(Charset::Gb2312.decode(0x4A40), Some('世' as u32));
+        assert_eq!(Charset::Big5.encode('一' as u32), Some(0xA440));
+        assert_eq!(Charset::Big5.decode(0xA440), Some('一' as u32));
+        for charset in [Charset::JisX0208, Charset::Gb2312, Charset::Big5] {
+            // ASCII is in another font of the same family
+            assert_eq!(charset.encode('A' as u32), None);
+            assert_eq!(charset.encode(0x1F600), None);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! `embedded-io` font source, enabled by the `embedded-io` feature.
+
+use core::cell::RefCell;
+
+use embedded_io::{Read, Seek, SeekFrom};
+
+use crate::{Error, FontSource};
+
+/// A font read from an `embedded-io` readable & seekable object, e.g. a file on an SD card.
+///
+/// The reader doesn't need to be `Clone`, reads are serialized by a [RefCell].
+pub struct EmbeddedIoSource<R> {
+    reader: RefCell<R>,
+}
+
+impl<R> EmbeddedIoSource<R>
+where
+    R: Read + Seek,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: RefCell::new(reader),
+        }
+    }
+
+    /// Gets the reader back.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R> FontSource for EmbeddedIoSource<R>
+where
+    R: Read + Seek,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let mut reader = self.reader.try_borrow_mut().map_err(|_| Error::Other)?;
+        reader
+            .seek(SeekFrom::Start(offset))
+            .map_err(|_| Error::Io)?;
+        reader.read_exact(buf).map_err(|_| Error::Io)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        load_pcf_font,
+        test_utils::{SlowReader, FONT_LATIN1_LSBYTE},
+    };
+
+    use super::*;
+
+    #[test]
+    fn reading_embedded_io() {
+        let data = FONT_LATIN1_LSBYTE;
+        let source = EmbeddedIoSource::new(SlowReader::new(data));
+        let font = load_pcf_font(source).unwrap();
+        let expected = load_pcf_font(data).unwrap();
+        let mut buffer: [u8; 32] = [0; 32];
+        let mut expected_buffer: [u8; 32] = [0; 32];
+        for c in "Aé~".chars() {
+            assert_eq!(
+                font.read_glyph_raw(c as u32, &mut buffer),
+                expected.read_glyph_raw(c as u32, &mut expected_buffer)
+            );
+            assert_eq!(buffer, expected_buffer);
+        }
+        assert_eq!(
+            font.source.read_at(data.len() as u64, &mut [0]),
+            Err(Error::Io)
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Typed font metadata.
+//!
+//! [FontInfo] collects the standard X11 font properties, falling back to the fields of the
+//! XLFD font name(the `FONT` property) for those missing.
+//!
+//! XLFD reference: [https://www.x.org/releases/X11R7.6/doc/xorg-docs/specs/XLFD/xlfd.html](https://www.x.org/releases/X11R7.6/doc/xorg-docs/specs/XLFD/xlfd.html)
+
+use crate::{Charset, Error, FontSource, PcfFont, Property, PropertyString, PropertyValue};
+
+/// An XLFD name has exactly 14 fields, each one led by a hyphen.
+const XLFD_FIELD_COUNT: usize = 14;
+
+/// Slant of the font design, the `SLANT` property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slant {
+    /// `R`, upright design
+    Roman,
+    /// `I`, italic design slanted clockwise
+    Italic,
+    /// `O`, obliqued upright design slanted clockwise
+    Oblique,
+    /// `RI`, italic design slanted counter-clockwise
+    ReverseItalic,
+    /// `RO`, obliqued upright design slanted counter-clockwise
+    ReverseOblique,
+    /// `OT`, other
+    Other,
+}
+
+impl Slant {
+    /// Parse the slant code, case insensitive.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        let slant = match code {
+            [b'R' | b'r'] => Self::Roman,
+            [b'I' | b'i'] => Self::Italic,
+            [b'O' | b'o'] => Self::Oblique,
+            [b'R' | b'r', b'I' | b'i'] => Self::ReverseItalic,
+            [b'R' | b'r', b'O' | b'o'] => Self::ReverseOblique,
+            [b'O' | b'o', b'T' | b't'] => Self::Other,
+            _ => return None,
+        };
+        Some(slant)
+    }
+}
+
+/// A parsed XLFD font name, like
+/// `-misc-fixed-medium-r-normal--13-120-75-75-c-70-iso10646-1`.
+///
+/// Fields are kept as offsets into the name, so no allocation happens.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Xlfd {
+    name: PropertyString,
+    /// `(start, end)` of each field in `name`
+    fields: [(u8, u8); XLFD_FIELD_COUNT],
+}
+
+impl Xlfd {
+    /// Parse an XLFD font name, `None` if it's not well formed.
+    pub fn parse(name: PropertyString) -> Option<Self> {
+        let bytes = name.as_bytes();
+        // fields are in ISO8859-1, only accept the ASCII/UTF-8 compatible ones
+        name.as_str()?;
+        if bytes.first() != Some(&b'-') {
+            return None;
+        }
+        let mut fields = [(0, 0); XLFD_FIELD_COUNT];
+        let mut count = 0;
+        let mut start = 1;
+        for (i, byte) in bytes.iter().enumerate().skip(1) {
+            if *byte == b'-' {
+                if count >= XLFD_FIELD_COUNT - 1 {
+                    return None;
+                }
+                fields[count] = (start as u8, i as u8);
+                count += 1;
+                start = i + 1;
+            }
+        }
+        if count != XLFD_FIELD_COUNT - 1 {
+            return None;
+        }
+        fields[count] = (start as u8, bytes.len() as u8);
+        Some(Self { name, fields })
+    }
+
+    /// The whole font name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        // checked when parsing
+        self.name.as_str().unwrap_or_default()
+    }
+
+    #[inline]
+    fn field(&self, index: usize) -> &str {
+        let (start, end) = self.fields[index];
+        &self.name()[start as usize..end as usize]
+    }
+
+    /// Numeric field, `None` if it's empty or a wildcard.
+    #[inline]
+    fn integer_field(&self, index: usize) -> Option<i32> {
+        self.field(index).parse().ok()
+    }
+
+    pub fn foundry(&self) -> &str {
+        self.field(0)
+    }
+
+    pub fn family_name(&self) -> &str {
+        self.field(1)
+    }
+
+    pub fn weight_name(&self) -> &str {
+        self.field(2)
+    }
+
+    pub fn slant(&self) -> Option<Slant> {
+        Slant::from_code(self.field(3).as_bytes())
+    }
+
+    pub fn setwidth_name(&self) -> &str {
+        self.field(4)
+    }
+
+    pub fn add_style_name(&self) -> &str {
+        self.field(5)
+    }
+
+    /// Body size in pixels
+    pub fn pixel_size(&self) -> Option<i32> {
+        self.integer_field(6)
+    }
+
+    /// Body size in decipoints
+    pub fn point_size(&self) -> Option<i32> {
+        self.integer_field(7)
+    }
+
+    /// Horizontal resolution in dots per inch
+    pub fn resolution_x(&self) -> Option<i32> {
+        self.integer_field(8)
+    }
+
+    /// Vertical resolution in dots per inch
+    pub fn resolution_y(&self) -> Option<i32> {
+        self.integer_field(9)
+    }
+
+    /// `P` for proportional, `M` for monospaced, `C` for character cell
+    pub fn spacing(&self) -> &str {
+        self.field(10)
+    }
+
+    /// Average width in tenths of pixels
+    pub fn average_width(&self) -> Option<i32> {
+        self.integer_field(11)
+    }
+
+    pub fn charset_registry(&self) -> &str {
+        self.field(12)
+    }
+
+    pub fn charset_encoding(&self) -> &str {
+        self.field(13)
+    }
+}
+
+impl core::fmt::Debug for Xlfd {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Xlfd").field(&self.name()).finish()
+    }
+}
+
+/// Font metadata from the properties table.
+///
+/// Every field is optional as no property is mandatory in PCF.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct FontInfo {
+    pub family_name: Option<PropertyString>,
+    pub weight_name: Option<PropertyString>,
+    pub slant: Option<Slant>,
+    /// Body size in pixels
+    pub pixel_size: Option<i32>,
+    /// Body size in decipoints
+    pub point_size: Option<i32>,
+    /// Horizontal resolution in dots per inch
+    pub resolution_x: Option<i32>,
+    /// Vertical resolution in dots per inch
+    pub resolution_y: Option<i32>,
+    pub charset_registry: Option<PropertyString>,
+    pub charset_encoding: Option<PropertyString>,
+    pub copyright: Option<PropertyString>,
+    /// The parsed `FONT` property
+    pub xlfd: Option<Xlfd>,
+}
+
+impl FontInfo {
+    /// No information at all.
+    const fn empty() -> Self {
+        Self {
+            family_name: None,
+            weight_name: None,
+            slant: None,
+            pixel_size: None,
+            point_size: None,
+            resolution_x: None,
+            resolution_y: None,
+            charset_registry: None,
+            charset_encoding: None,
+            copyright: None,
+            xlfd: None,
+        }
+    }
+
+    /// The charset of the font, [Charset::Unknown] if the charset properties are missing.
+    pub fn charset(&self) -> Charset {
+        charset_from_properties(self.charset_registry, self.charset_encoding, self.xlfd)
+    }
+
+    /// Take the property into account, if it's a known one.
+    fn add_property(&mut self, property: Property) {
+        let string = property.value.as_string().copied();
+        let integer = property.value.as_integer();
+        match (property.name.as_bytes(), property.value) {
+            (b"FAMILY_NAME", _) => self.family_name = string,
+            (b"WEIGHT_NAME", _) => self.weight_name = string,
+            (b"SLANT", PropertyValue::String(value)) => {
+                self.slant = Slant::from_code(value.as_bytes())
+            }
+            (b"PIXEL_SIZE", _) => self.pixel_size = integer,
+            (b"POINT_SIZE", _) => self.point_size = integer,
+            (b"RESOLUTION_X", _) => self.resolution_x = integer,
+            (b"RESOLUTION_Y", _) => self.resolution_y = integer,
+            (b"CHARSET_REGISTRY", _) => self.charset_registry = string,
+            (b"CHARSET_ENCODING", _) => self.charset_encoding = string,
+            (b"COPYRIGHT", _) => self.copyright = string,
+            (b"FONT", PropertyValue::String(value)) => self.xlfd = Xlfd::parse(value),
+            _ => {}
+        }
+    }
+
+    /// Fill the missing fields with the XLFD name fields.
+    fn fill_from_xlfd(&mut self) {
+        let Some(xlfd) = self.xlfd else {
+            return;
+        };
+        let non_empty = |value: &str| {
+            if value.is_empty() {
+                None
+            } else {
+                Some(PropertyString::from_bytes(value.as_bytes()))
+            }
+        };
+        self.family_name = self.family_name.or_else(|| non_empty(xlfd.family_name()));
+        self.weight_name = self.weight_name.or_else(|| non_empty(xlfd.weight_name()));
+        self.slant = self.slant.or_else(|| xlfd.slant());
+        self.pixel_size = self.pixel_size.or_else(|| xlfd.pixel_size());
+        self.point_size = self.point_size.or_else(|| xlfd.point_size());
+        self.resolution_x = self.resolution_x.or_else(|| xlfd.resolution_x());
+        self.resolution_y = self.resolution_y.or_else(|| xlfd.resolution_y());
+        self.charset_registry = self
+            .charset_registry
+            .or_else(|| non_empty(xlfd.charset_registry()));
+        self.charset_encoding = self
+            .charset_encoding
+            .or_else(|| non_empty(xlfd.charset_encoding()));
+    }
+}
+
+/// Just the properties the charset comes from, much smaller than a [FontInfo].
+#[derive(Default)]
+pub(crate) struct CharsetProperties {
+    registry: Option<PropertyString>,
+    encoding: Option<PropertyString>,
+    font: Option<PropertyString>,
+}
+
+impl CharsetProperties {
+    pub(crate) fn add_property(&mut self, property: Property) {
+        let PropertyValue::String(value) = property.value else {
+            return;
+        };
+        match property.name.as_bytes() {
+            b"CHARSET_REGISTRY" => self.registry = Some(value),
+            b"CHARSET_ENCODING" => self.encoding = Some(value),
+            b"FONT" => self.font = Some(value),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn charset(&self) -> Charset {
+        let xlfd = self.font.and_then(Xlfd::parse);
+        charset_from_properties(self.registry, self.encoding, xlfd)
+    }
+}
+
+/// The charset from the charset properties, falling back to the fields of the XLFD name.
+///
+/// Shared by [FontInfo::charset] and the font loaders, which look up only these properties.
+pub(crate) fn charset_from_properties(
+    registry: Option<PropertyString>,
+    encoding: Option<PropertyString>,
+    xlfd: Option<Xlfd>,
+) -> Charset {
+    let xlfd = xlfd.as_ref();
+    fn non_empty(value: &str) -> Option<&[u8]> {
+        (!value.is_empty()).then_some(value.as_bytes())
+    }
+    let registry = registry.as_ref().map(PropertyString::as_bytes);
+    let registry = registry.or_else(|| non_empty(xlfd?.charset_registry()));
+    let encoding = encoding.as_ref().map(PropertyString::as_bytes);
+    let encoding = encoding.or_else(|| non_empty(xlfd?.charset_encoding()));
+    match (registry, encoding) {
+        (Some(registry), Some(encoding)) => Charset::from_registry(registry, encoding),
+        _ => Charset::Unknown,
+    }
+}
+
+impl<T> PcfFont<T>
+where
+    T: FontSource,
+{
+    /// The charset of the font, [Charset::Unknown] if a property can't be read.
+    pub(crate) fn detect_charset(&self) -> Charset {
+        let mut properties = CharsetProperties::default();
+        for property in self.properties() {
+            let Ok(property) = property else {
+                return Charset::Unknown;
+            };
+            properties.add_property(property);
+        }
+        properties.charset()
+    }
+
+    /// Collect the font metadata, with a single pass over the properties table.
+    pub fn font_info(&self) -> Result<FontInfo, Error> {
+        let mut info = FontInfo::empty();
+        for property in self.properties() {
+            info.add_property(property?);
+        }
+        info.fill_from_xlfd();
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+
+    use crate::load_pcf_font;
+
+    use super::*;
+
+    /// Big endian, glyph row padded to int(4 bytes)
+    const FONT_VARIABLE: &[u8] =
+        include_bytes!("../test-fonts/fusion-pixel-12px-proportional-zh_hans-pad_to_int.pcf");
+
+    #[test]
+    fn parsing_xlfd() {
+        let name = "-misc-fixed-medium-r-semicondensed--13-120-75-75-c-60-iso10646-1";
+        let xlfd = Xlfd::parse(PropertyString::from_bytes(name.as_bytes())).unwrap();
+        assert_eq!(xlfd.name(), name);
+        assert_eq!(xlfd.foundry(), "misc");
+        assert_eq!(xlfd.family_name(), "fixed");
+        assert_eq!(xlfd.slant(), Some(Slant::Roman));
+        assert_eq!(xlfd.add_style_name(), "");
+        assert_eq!(xlfd.pixel_size(), Some(13));
+        assert_eq!(xlfd.average_width(), Some(60));
+        assert_eq!(xlfd.charset_registry(), "iso10646");
+        assert_eq!(xlfd.charset_encoding(), "1");
+
+        for malformed in [
+            "misc-fixed",
+            "-misc-fixed-medium-r",
+            "-a-b-c-d-e-f-g-h-i-j-k-l-m-n-o",
+        ] {
+            assert!(Xlfd::parse(PropertyString::from_bytes(malformed.as_bytes())).is_none());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_reading_font_info() {
+        let font = load_pcf_font(Cursor::new(FONT_VARIABLE)).unwrap();
+        let info = font.font_info().unwrap();
+        assert_eq!(
+            info.family_name.unwrap().as_str(),
+            Some("Fusion Pixel 12px Proportional zh_hans")
+        );
+        assert_eq!(info.weight_name.unwrap().as_str(), Some("Regular"));
+        assert_eq!(info.slant, Some(Slant::Roman));
+        assert_eq!(info.pixel_size, Some(12));
+        assert_eq!(info.point_size, Some(120));
+        assert_eq!(info.resolution_x, Some(75));
+        assert_eq!(info.resolution_y, Some(75));
+        assert_eq!(info.charset_registry.unwrap().as_str(), Some("ISO10646"));
+        assert_eq!(info.charset_encoding.unwrap().as_str(), Some("1"));
+        assert_eq!(info.charset(), Charset::Unicode);
+        assert!(info.copyright.is_some());
+        let xlfd = info.xlfd.unwrap();
+        assert_eq!(xlfd.foundry(), "TakWolf");
+        assert_eq!(xlfd.add_style_name(), "Sans Serif");
+        assert_eq!(xlfd.spacing(), "P");
+    }
+}
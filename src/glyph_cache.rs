@@ -0,0 +1,237 @@
+//! Glyph bitmap cache, so redrawing the same text doesn't read the font again.
+//!
+//! The cache doesn't allocate, the slots and the glyph data live in arrays supplied by the caller.
+
+use core::cell::RefCell;
+
+use crate::{Error, FontSource, MetricsEntry, PcfFont};
+
+/// Bookkeeping of a cached glyph, see [GlyphCache::new].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphCacheSlot {
+    /// `(font index, code point)`, `None` if the slot is free
+    key: Option<(usize, u32)>,
+    metrics: MetricsEntry,
+    length: usize,
+    last_used: u32,
+}
+
+impl GlyphCacheSlot {
+    /// A free slot, to initialize the slots array.
+    pub const EMPTY: Self = Self {
+        key: None,
+        metrics: MetricsEntry {
+            left_side_bearing: 0,
+            right_side_bearing: 0,
+            character_width: 0,
+            character_ascent: 0,
+            character_descent: 0,
+            character_attributes: 0,
+        },
+        length: 0,
+        last_used: 0,
+    };
+}
+
+#[derive(Debug, PartialEq)]
+struct Slots<'c> {
+    slots: &'c mut [GlyphCacheSlot],
+    data: &'c mut [u8],
+    /// Increased on each access, to find the least recently used slot
+    clock: u32,
+}
+
+impl Slots<'_> {
+    fn slot_size(&self) -> usize {
+        self.data.len().checked_div(self.slots.len()).unwrap_or(0)
+    }
+
+    /// Index of the slot holding the glyph, marking it used.
+    fn find(&mut self, key: (usize, u32)) -> Option<usize> {
+        let index = self.slots.iter().position(|slot| slot.key == Some(key))?;
+        // a wrap only makes a few evictions choose the wrong slot
+        self.clock = self.clock.wrapping_add(1);
+        self.slots[index].last_used = self.clock;
+        Some(index)
+    }
+}
+
+/// Fixed-capacity LRU cache of glyph bitmaps & metrics, keyed by font and code point.
+///
+/// Enabled with [crate::PcfFontStyleBuilder::glyph_cache], it mostly helps fonts read from slow
+/// storages, like a NOR flash. Fonts are told apart by their index in the style, the main font
+/// being 0 and the fallback fonts following. Styles sharing a cache must use the same fonts, call
+/// [GlyphCache::clear] before switching fonts.
+///
+/// ```rust,ignore
+/// let mut slots = [GlyphCacheSlot::EMPTY; 16];
+/// let mut data = [0; 16 * 32];
+/// let cache = GlyphCache::new(&mut slots, &mut data);
+/// let style = PcfFontStyleBuilder::new(&font)
+///     .text_color(BinaryColor::On)
+///     .glyph_cache(&cache)
+///     .build();
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct GlyphCache<'c> {
+    inner: RefCell<Slots<'c>>,
+}
+
+impl<'c> GlyphCache<'c> {
+    /// Create an empty cache holding up to `slots.len()` glyphs.
+    ///
+    /// `data` is split evenly between the slots, glyphs bigger than `data.len() / slots.len()`
+    /// bytes are never cached. [PcfFont::max_bytes_per_glyph] gives a size fitting every glyph.
+    pub fn new(slots: &'c mut [GlyphCacheSlot], data: &'c mut [u8]) -> Self {
+        slots.fill(GlyphCacheSlot::EMPTY);
+        Self {
+            inner: RefCell::new(Slots {
+                slots,
+                data,
+                clock: 0,
+            }),
+        }
+    }
+
+    /// Drop all the cached glyphs.
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.inner.try_borrow_mut() {
+            inner.slots.fill(GlyphCacheSlot::EMPTY);
+        }
+    }
+
+    /// Metrics of the glyph of the font at `font_index`, if it's cached.
+    pub(crate) fn metrics(&self, font_index: usize, code_point: u32) -> Option<MetricsEntry> {
+        let mut inner = self.inner.try_borrow_mut().ok()?;
+        let index = inner.find((font_index, code_point))?;
+        Some(inner.slots[index].metrics)
+    }
+
+    /// Call `f` with the glyph bitmap and metrics, reading the glyph from the font on a miss.
+    /// `font_index` is the index of `font` in the style.
+    ///
+    /// Returns [Error::BufferTooSmall] if the glyph doesn't fit in a slot, and [Error::Other] if
+    /// the cache is already in use.
+    pub(crate) fn with_glyph<T, R>(
+        &self,
+        font: &PcfFont<T>,
+        font_index: usize,
+        code_point: u32,
+        f: impl FnOnce(&[u8], MetricsEntry) -> R,
+    ) -> Result<R, Error>
+    where
+        T: FontSource,
+    {
+        let mut inner = self.inner.try_borrow_mut().map_err(|_| Error::Other)?;
+        let slot_size = inner.slot_size();
+        let key = (font_index, code_point);
+        let index = match inner.find(key) {
+            Some(index) => index,
+            None => {
+                // a free slot, or the least recently used one
+                let clock = inner.clock;
+                let index = inner
+                    .slots
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, slot)| match slot.key {
+                        None => u32::MAX,
+                        Some(_) => clock.wrapping_sub(slot.last_used),
+                    })
+                    .map(|(index, _)| index)
+                    .ok_or(Error::BufferTooSmall)?;
+                let start = index * slot_size;
+                let data = &mut inner.data[start..start + slot_size];
+                let (length, metrics) = match font.read_glyph_raw(code_point, data) {
+                    Ok(glyph) => glyph,
+                    // found before reading, the slot is still intact
+                    Err(error @ (Error::NotFound | Error::BufferTooSmall)) => return Err(error),
+                    Err(error) => {
+                        inner.slots[index] = GlyphCacheSlot::EMPTY;
+                        return Err(error);
+                    }
+                };
+                inner.clock = clock.wrapping_add(1);
+                inner.slots[index] = GlyphCacheSlot {
+                    key: Some(key),
+                    metrics,
+                    length,
+                    last_used: inner.clock,
+                };
+                index
+            }
+        };
+        let slot = inner.slots[index];
+        let start = index * slot_size;
+        Ok(f(&inner.data[start..start + slot.length], slot.metrics))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        load_pcf_font,
+        test_utils::{CountingSource, FONT_LATIN1_LSBYTE},
+    };
+
+    use super::*;
+
+    #[test]
+    fn caching_glyphs() {
+        let source = CountingSource::new(FONT_LATIN1_LSBYTE);
+        let font = load_pcf_font(&source).unwrap();
+        let mut slots = [GlyphCacheSlot::EMPTY; 2];
+        let mut data = [0; 2 * 32];
+        let cache = GlyphCache::new(&mut slots, &mut data);
+
+        let mut expected_buffer: [u8; 32] = [0; 32];
+        // count the reads needed for a glyph
+        let mut read_glyph = |c: char| {
+            source.reads.set(0);
+            let glyph = cache.with_glyph(&font, 0, c as u32, |data, metrics| {
+                let (length, expected_metrics) =
+                    font.read_glyph_raw(c as u32, &mut expected_buffer).unwrap();
+                assert_eq!(data, &expected_buffer[..length], "bitmap of {c:?}");
+                assert_eq!(metrics, expected_metrics, "metrics of {c:?}");
+            });
+            assert_eq!(glyph, Ok(()));
+            source.reads.get()
+        };
+        let uncached = read_glyph('A');
+        // only the check in the closure reads
+        assert!(read_glyph('A') < uncached);
+        let cached = read_glyph('A');
+        read_glyph('B');
+        read_glyph('A');
+        // 'B' is the least recently used
+        read_glyph('C');
+        assert_eq!(read_glyph('A'), cached);
+        assert_eq!(read_glyph('C'), cached);
+        assert!(read_glyph('B') > cached);
+        assert!(cache.metrics(0, 'B' as u32).is_some());
+        assert_eq!(cache.metrics(0, 'A' as u32), None);
+
+        assert_eq!(
+            cache.with_glyph(&font, 0, 0x4E16, |_, _| ()),
+            Err(Error::NotFound)
+        );
+        assert!(cache.metrics(0, 'B' as u32).is_some());
+        // the same character of another font is another glyph
+        assert_eq!(cache.metrics(1, 'B' as u32), None);
+        assert_eq!(cache.with_glyph(&font, 1, 'B' as u32, |_, _| ()), Ok(()));
+        assert!(cache.metrics(1, 'B' as u32).is_some());
+        assert!(cache.metrics(0, 'B' as u32).is_some());
+        cache.clear();
+        assert_eq!(cache.metrics(0, 'B' as u32), None);
+
+        // glyphs not fitting in a slot are never cached
+        let mut slots = [GlyphCacheSlot::EMPTY; 4];
+        let mut data = [0; 4];
+        let cache = GlyphCache::new(&mut slots, &mut data);
+        assert_eq!(
+            cache.with_glyph(&font, 0, 'A' as u32, |_, _| ()),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(cache.metrics(0, 'A' as u32), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! PCF glyph names table.
+//!
+//! Every glyph may have a name, like `.notdef` or `uni4E16`, which is the only way to reach
+//! the glyphs without a code point.
+
+use crate::{
+    parser::TableTocEntry,
+    source::{FontSource, SourceReader},
+    utils::ByteOrder,
+    Error, PcfFont,
+};
+
+/// Location of the glyph names table contents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GlyphNamesTable {
+    byte_order: ByteOrder,
+    count: u32,
+    /// The absolute offset to the name offsets look up table
+    offsets_location: u64,
+    /// The absolute offset to the string pool
+    strings_location: u64,
+    strings_size: u32,
+}
+
+impl GlyphNamesTable {
+    /// Load the glyph names table header described by `toc`.
+    pub(crate) fn load<T>(
+        cursor: &mut SourceReader<'_, T>,
+        toc: TableTocEntry,
+    ) -> Result<Self, Error>
+    where
+        T: FontSource + ?Sized,
+    {
+        let byte_order = toc.byte_order();
+        let mut buffer: [u8; 4] = [0; 4];
+        // skip format field
+        cursor.seek(toc.locate(4, 4)?);
+        cursor.read_exact(&mut buffer)?;
+        let count = byte_order.u32_from_bytes_ref(&buffer);
+        // NOTE: each name offset takes 4 bytes(u32), followed by the string pool size
+        let offsets_size = count as u64 * 4;
+        let offsets_location = toc.locate(4 + 4, offsets_size)?;
+        cursor.seek(toc.locate(4 + 4 + offsets_size, 4)?);
+        cursor.read_exact(&mut buffer)?;
+        let strings_size = byte_order.u32_from_bytes_ref(&buffer);
+        let strings_location = toc.locate(4 + 4 + offsets_size + 4, strings_size as u64)?;
+        Ok(Self {
+            byte_order,
+            count,
+            offsets_location,
+            strings_location,
+            strings_size,
+        })
+    }
+
+    /// Seek to the name of the glyph, returns the length of the string pool left.
+    fn seek_name<T>(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+    ) -> Result<usize, Error>
+    where
+        T: FontSource + ?Sized,
+    {
+        if glyph_index as u32 >= self.count {
+            return Err(Error::NotFound);
+        }
+        let mut buffer: [u8; 4] = [0; 4];
+        cursor.seek(self.offsets_location + glyph_index as u64 * 4);
+        cursor.read_exact(&mut buffer)?;
+        let name_offset = self.byte_order.u32_from_bytes_ref(&buffer);
+        if name_offset >= self.strings_size {
+            return Err(Error::CorruptedData);
+        }
+        cursor.seek(self.strings_location + name_offset as u64);
+        Ok((self.strings_size - name_offset) as usize)
+    }
+}
+
+/// Compare the NUL terminated string at the cursor with `name`, reading at most `available`
+/// bytes.
+fn name_matches<T>(
+    cursor: &mut SourceReader<'_, T>,
+    name: &[u8],
+    mut available: usize,
+) -> Result<bool, Error>
+where
+    T: FontSource + ?Sized,
+{
+    let mut expected = name.iter().copied().chain(core::iter::once(0));
+    let mut buffer: [u8; 16] = [0; 16];
+    loop {
+        let count = available.min(buffer.len());
+        if count == 0 {
+            return Ok(false);
+        }
+        cursor.read_exact(&mut buffer[..count])?;
+        available -= count;
+        for byte in buffer[..count].iter() {
+            match expected.next() {
+                Some(0) if *byte == 0 => return Ok(true),
+                Some(value) if value == *byte => {}
+                _ => return Ok(false),
+            }
+        }
+    }
+}
+
+impl<T> PcfFont<T>
+where
+    T: FontSource,
+{
+    /// Find the internal glyph index by the glyph name, e.g. `.notdef`.
+    ///
+    /// Returns [Error::NotFound] if the font has no glyph names table or no glyph matches.
+    pub fn glyph_index_by_name(&self, name: &str) -> Result<u16, Error> {
+        let table = self.glyph_names_table.ok_or(Error::NotFound)?;
+        let mut cursor = SourceReader::new(&self.source);
+        // glyph index is u16 in the encoding table
+        let count = table.count.min(u16::MAX as u32) as u16;
+        for glyph_index in 0..count {
+            let available = table.seek_name(&mut cursor, glyph_index)?;
+            if name_matches(&mut cursor, name.as_bytes(), available)? {
+                return Ok(glyph_index);
+            }
+        }
+        Err(Error::NotFound)
+    }
+
+    /// Read the name of the glyph at the internal glyph index into `buf`.
+    pub fn glyph_name<'b>(&self, glyph_index: u16, buf: &'b mut [u8]) -> Result<&'b str, Error> {
+        let table = self.glyph_names_table.ok_or(Error::NotFound)?;
+        let mut cursor = SourceReader::new(&self.source);
+        let available = table.seek_name(&mut cursor, glyph_index)?;
+        let mut length = 0;
+        loop {
+            if length == available {
+                // the string pool ends without a terminator
+                return Err(Error::CorruptedData);
+            }
+            if length == buf.len() {
+                return Err(Error::BufferTooSmall);
+            }
+            // read in chunks, names are usually short
+            let end = buf.len().min(available).min(length + 32);
+            let count = end - length;
+            cursor.read_exact(&mut buf[length..end])?;
+            if let Some(end) = buf[length..length + count].iter().position(|b| *b == 0) {
+                length += end;
+                break;
+            }
+            length += count;
+        }
+        core::str::from_utf8(&buf[..length]).map_err(|_| Error::CorruptedData)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+
+    use crate::{load_pcf_font, test_utils::FONT_LATIN1_LSBYTE};
+
+    use super::*;
+
+    /// Big endian, glyph row padded to int(4 bytes)
+    const FONT_VARIABLE: &[u8] =
+        include_bytes!("../test-fonts/fusion-pixel-12px-proportional-zh_hans-pad_to_int.pcf");
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_looking_up_glyph_names() {
+        for data in [FONT_VARIABLE, FONT_LATIN1_LSBYTE] {
+            let font = load_pcf_font(Cursor::new(data)).unwrap();
+            let mut name_buffer: [u8; 32] = [0; 32];
+            assert_eq!(font.glyph_index_by_name(".notdef"), Ok(0));
+            assert_eq!(font.glyph_name(0, &mut name_buffer), Ok(".notdef"));
+            assert_eq!(
+                font.glyph_name(0, &mut name_buffer[..4]),
+                Err(Error::BufferTooSmall)
+            );
+            assert_eq!(font.glyph_index_by_name(".notde"), Err(Error::NotFound));
+            assert_eq!(font.glyph_index_by_name(".notdefs"), Err(Error::NotFound));
+
+            let glyph_index = font.glyph_index_by_name("uni0041").unwrap();
+            assert_eq!(font.glyph_index('A' as u32), Ok(glyph_index));
+            assert_eq!(
+                font.glyph_name(glyph_index, &mut name_buffer),
+                Ok("uni0041")
+            );
+
+            // unencoded glyphs are readable by index
+            let mut buffer: [u8; 50] = [0; 50];
+            let (_, metrics) = font.read_glyph_raw_by_index(0, &mut buffer).unwrap();
+            assert_eq!(font.get_glyph_metrics_by_index(0), Ok(metrics));
+            let glyph_count = font.glyph_count() as u16;
+            assert_eq!(
+                font.read_glyph_raw_by_index(glyph_count, &mut buffer),
+                Err(Error::NotFound)
+            );
+        }
+    }
+}
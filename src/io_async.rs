@@ -0,0 +1,409 @@
+//! Async font reading with `embedded-io-async`, enabled by the `embedded-io-async` feature.
+//!
+//! The parser itself is synchronous. Each lookup runs against the blocks of the font fetched so
+//! far, whenever some data is missing the blocks are read asynchronously and the lookup runs
+//! again. Glyph bitmaps are read asynchronously as they are, a chunk of a row at a time. So the
+//! executor is never blocked while waiting for the storage.
+
+use core::cell::Cell;
+
+use embedded_io_async::{Read, Seek, SeekFrom};
+
+use crate::{
+    font_info::CharsetProperties, parser::load_tables, Error, FontSource, MetricsEntry, PcfFont,
+};
+
+/// Size of each block read from the storage.
+const BLOCK_SIZE: usize = 32;
+/// Maximum number of blocks a single lookup can use, far more than any lookup needs.
+const BLOCK_COUNT: usize = 32;
+/// Glyph rows are read in chunks of this size, a multiple of any scan unit.
+const ROW_CHUNK_SIZE: usize = 64;
+
+/// Blocks of the font fetched during a single lookup.
+struct BlockCache {
+    /// `(block index, valid length)` of each block, shorter blocks are at the end of the data
+    blocks: [(u64, usize); BLOCK_COUNT],
+    data: [[u8; BLOCK_SIZE]; BLOCK_COUNT],
+    count: usize,
+    /// `(first, last)` blocks of the first read found missing data since the last fetch
+    missing: Cell<Option<(u64, u64)>>,
+}
+
+impl BlockCache {
+    fn new() -> Self {
+        Self {
+            blocks: [(0, 0); BLOCK_COUNT],
+            data: [[0; BLOCK_SIZE]; BLOCK_COUNT],
+            count: 0,
+            missing: Cell::new(None),
+        }
+    }
+
+    fn block(&self, index: u64) -> Option<usize> {
+        self.blocks[..self.count]
+            .iter()
+            .position(|(i, _)| *i == index)
+    }
+
+    /// Read the blocks from the storage, skipping those fetched already.
+    async fn fetch<R>(&mut self, reader: &mut R, (first, last): (u64, u64)) -> Result<(), Error>
+    where
+        R: Read + Seek,
+    {
+        for index in first..=last {
+            if self.block(index).is_some() {
+                continue;
+            }
+            if self.count == BLOCK_COUNT {
+                return Err(Error::BufferTooSmall);
+            }
+            let data = &mut self.data[self.count];
+            let length = read_at(reader, index * BLOCK_SIZE as u64, data).await?;
+            self.blocks[self.count] = (index, length);
+            self.count += 1;
+            if length < BLOCK_SIZE {
+                // the end of the data
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fill `buf` with the data at `offset`, returns the length read, shorter at the end of the data.
+async fn read_at<R>(reader: &mut R, offset: u64, buf: &mut [u8]) -> Result<usize, Error>
+where
+    R: Read + Seek,
+{
+    reader
+        .seek(SeekFrom::Start(offset))
+        .await
+        .map_err(|_| Error::Io)?;
+    // reads may be short, stop only at the end of the data
+    let mut length = 0;
+    while length < buf.len() {
+        match reader.read(&mut buf[length..]).await {
+            Ok(0) => break,
+            Ok(count) => length += count,
+            Err(_) => return Err(Error::Io),
+        }
+    }
+    Ok(length)
+}
+
+/// The data of a glyph row read by [AsyncPcfFont], at its offset in the font.
+struct RowChunk<'a> {
+    offset: u64,
+    data: &'a [u8],
+}
+
+impl FontSource for RowChunk<'_> {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let data = offset
+            .checked_sub(self.offset)
+            .and_then(|start| {
+                self.data
+                    .get(usize::try_from(start).ok()?..)?
+                    .get(..buf.len())
+            })
+            .ok_or(Error::Io)?;
+        buf.copy_from_slice(data);
+        Ok(())
+    }
+}
+
+impl FontSource for BlockCache {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let mut position = offset;
+        let mut done = 0;
+        while done < buf.len() {
+            let index = position / BLOCK_SIZE as u64;
+            let start = (position % BLOCK_SIZE as u64) as usize;
+            let Some(block) = self.block(index) else {
+                // the rest of the read is fetched at once
+                let end = position.saturating_add((buf.len() - done - 1) as u64);
+                if self.missing.get().is_none() {
+                    self.missing.set(Some((index, end / BLOCK_SIZE as u64)));
+                }
+                return Err(Error::Io);
+            };
+            let length = self.blocks[block].1;
+            if start >= length {
+                // beyond the end of the data
+                return Err(Error::Io);
+            }
+            let count = (length - start).min(buf.len() - done);
+            buf[done..done + count].copy_from_slice(&self.data[block][start..start + count]);
+            done += count;
+            position += count as u64;
+        }
+        Ok(())
+    }
+}
+
+/// Run the lookup until all the data it needs is fetched.
+async fn run<R, O>(
+    reader: &mut R,
+    mut operation: impl FnMut(&BlockCache) -> Result<O, Error>,
+) -> Result<O, Error>
+where
+    R: Read + Seek,
+{
+    let mut cache = BlockCache::new();
+    loop {
+        let result = operation(&cache);
+        // some lookups recover from errors, so retry whenever data was missing
+        match cache.missing.take() {
+            Some(blocks) => cache.fetch(reader, blocks).await?,
+            None => return result,
+        }
+    }
+}
+
+/// A PCF font read asynchronously, created by [load_pcf_font_async].
+///
+/// Font-wide data is available from [AsyncPcfFont::font], glyphs are read with the async methods.
+pub struct AsyncPcfFont<R> {
+    font: PcfFont<()>,
+    reader: R,
+}
+
+/// Check and load PCF font from an `embedded-io-async` reader, e.g. a file on an SD card.
+pub async fn load_pcf_font_async<R>(mut reader: R) -> Result<AsyncPcfFont<R>, Error>
+where
+    R: Read + Seek,
+{
+    let mut font = run(&mut reader, |cache| {
+        load_tables(cache).map(|font| font.with_source(()))
+    })
+    .await?;
+    // one property at a time, the whole table may not fit in the cache
+    let mut properties = CharsetProperties::default();
+    for index in 0..font.property_count() {
+        let property = run(&mut reader, |cache| {
+            font.with_source(cache).property_at(index).transpose()
+        })
+        .await;
+        // a broken property only loses the charset, like load_pcf_font
+        let Ok(Some(property)) = property else {
+            properties = CharsetProperties::default();
+            break;
+        };
+        properties.add_property(property);
+    }
+    font.override_charset(properties.charset());
+    Ok(AsyncPcfFont { font, reader })
+}
+
+impl<R> AsyncPcfFont<R>
+where
+    R: Read + Seek,
+{
+    /// The font-wide data, like the bounding box and the accelerators.
+    #[inline]
+    pub fn font(&self) -> &PcfFont<()> {
+        &self.font
+    }
+
+    /// Same with [AsyncPcfFont::font], but allows changes like [PcfFont::override_charset].
+    #[inline]
+    pub fn font_mut(&mut self) -> &mut PcfFont<()> {
+        &mut self.font
+    }
+
+    /// Async version of [PcfFont::try_override_default_char], [PcfFont::override_default_char]
+    /// is available through [AsyncPcfFont::font_mut].
+    pub async fn try_override_default_char(&mut self, value: u16) -> Result<(), Error> {
+        let font = &self.font;
+        self.font = run(&mut self.reader, |cache| {
+            let mut font = font.with_source(cache);
+            font.try_override_default_char(value)?;
+            Ok(font.with_source(()))
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Gets the reader back.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Async version of [PcfFont::read_glyph_raw].
+    pub async fn read_glyph_raw(
+        &mut self,
+        code_point: u32,
+        buf: &mut [u8],
+    ) -> Result<(usize, MetricsEntry), Error> {
+        let font = &self.font;
+        let glyph_index = run(&mut self.reader, |cache| {
+            font.with_source(cache).glyph_index(code_point)
+        })
+        .await?;
+        self.read_glyph_raw_by_index(glyph_index, buf).await
+    }
+
+    /// Async version of [PcfFont::read_glyph_raw_by_index].
+    pub async fn read_glyph_raw_by_index(
+        &mut self,
+        glyph_index: u16,
+        buf: &mut [u8],
+    ) -> Result<(usize, MetricsEntry), Error> {
+        let font = &self.font;
+        let (location, stored_row_bytes, metrics) = run(&mut self.reader, |cache| {
+            font.with_source(cache).glyph_bitmap_by_index(glyph_index)
+        })
+        .await?;
+        // the size is checked already
+        let row_bytes = (metrics.glyph_width() as usize).div_ceil(8);
+        let length = metrics.glyph_height() as usize * row_bytes;
+        let glyph = buf.get_mut(..length).ok_or(Error::BufferTooSmall)?;
+        let mut chunk: [u8; ROW_CHUNK_SIZE] = [0; ROW_CHUNK_SIZE];
+        for (y, row) in glyph.chunks_exact_mut(row_bytes.max(1)).enumerate() {
+            let row_location = location + (y * stored_row_bytes) as u64;
+            for first_byte in (0..row_bytes).step_by(ROW_CHUNK_SIZE) {
+                let offset = row_location + first_byte as u64;
+                let data = &mut chunk[..(stored_row_bytes - first_byte).min(ROW_CHUNK_SIZE)];
+                if read_at(&mut self.reader, offset, data).await? < data.len() {
+                    return Err(Error::Io);
+                }
+                let part_end = (first_byte + ROW_CHUNK_SIZE).min(row_bytes);
+                font.with_source(RowChunk { offset, data }).read_row_part(
+                    row_location,
+                    first_byte,
+                    &mut row[first_byte..part_end],
+                )?;
+            }
+        }
+        Ok((length, metrics))
+    }
+
+    /// Async version of [PcfFont::get_glyph_metrics].
+    pub async fn get_glyph_metrics(&mut self, code_point: u32) -> Result<MetricsEntry, Error> {
+        let font = &self.font;
+        run(&mut self.reader, |cache| {
+            font.with_source(cache).get_glyph_metrics(code_point)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use crate::{
+        load_pcf_font,
+        test_utils::{
+            font_with_broken_properties, SlowReader, FONT_LATIN1_LSBIT_UNIT2, FONT_LATIN1_LSBYTE,
+        },
+        Charset,
+    };
+
+    use super::*;
+
+    /// Poll the future to the end, the test readers are always ready.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn reading_broken_properties_asynchronously() {
+        for broken_table in [true, false] {
+            let data = font_with_broken_properties(broken_table);
+            let reader = SlowReader::new(&data);
+            // only the charset is lost, like load_pcf_font
+            let font = block_on(load_pcf_font_async(reader)).unwrap();
+            assert_eq!(font.font().charset(), Charset::Unknown);
+            let expected = load_pcf_font(&data[..]).unwrap();
+            assert_eq!(font.font().glyph_count(), expected.glyph_count());
+        }
+    }
+
+    #[test]
+    fn reading_glyphs_asynchronously() {
+        let data = FONT_LATIN1_LSBYTE;
+        let reader = SlowReader::new(data);
+        let mut font = block_on(load_pcf_font_async(reader)).unwrap();
+        let expected = load_pcf_font(data).unwrap();
+        assert_eq!(font.font().bounding_box(), expected.bounding_box());
+        assert_eq!(font.font().glyph_count(), expected.glyph_count());
+        assert_eq!(font.font().charset(), Charset::Unicode);
+
+        let mut buffer: [u8; 32] = [0; 32];
+        let mut expected_buffer: [u8; 32] = [0; 32];
+        for c in "Aé~".chars() {
+            assert_eq!(
+                block_on(font.read_glyph_raw(c as u32, &mut buffer)),
+                expected.read_glyph_raw(c as u32, &mut expected_buffer)
+            );
+            assert_eq!(buffer, expected_buffer);
+            assert_eq!(
+                block_on(font.get_glyph_metrics(c as u32)),
+                expected.get_glyph_metrics(c as u32)
+            );
+        }
+        assert_eq!(
+            block_on(font.read_glyph_raw(0x4E16, &mut buffer)),
+            Err(Error::NotFound)
+        );
+
+        block_on(font.try_override_default_char('?' as u16)).unwrap();
+        assert_eq!(font.font().default_char(), Some(0x3F));
+        assert_eq!(
+            block_on(font.try_override_default_char(0x0141)),
+            Err(Error::NotFound)
+        );
+        assert_eq!(font.font().default_char(), Some(0x3F));
+
+        // all the glyphs, read directly a row at a time
+        let mut buffer: [u8; 50] = [0; 50];
+        let mut expected_buffer: [u8; 50] = [0; 50];
+        for data in [data, FONT_LATIN1_LSBIT_UNIT2] {
+            let reader = SlowReader::new(data);
+            let mut font = block_on(load_pcf_font_async(reader)).unwrap();
+            let expected = load_pcf_font(data).unwrap();
+            for glyph_index in 0..expected.glyph_count() as u16 {
+                assert_eq!(
+                    block_on(font.read_glyph_raw_by_index(glyph_index, &mut buffer)),
+                    expected.read_glyph_raw_by_index(glyph_index, &mut expected_buffer),
+                );
+                assert_eq!(buffer, expected_buffer);
+            }
+        }
+
+        let truncated = SlowReader::new(&data[..1000]);
+        assert!(block_on(load_pcf_font_async(truncated)).is_err());
+    }
+
+    #[test]
+    fn fetching_whole_reads_at_once() {
+        let data = FONT_LATIN1_LSBYTE;
+        let mut reader = SlowReader::new(data);
+        let mut runs = 0;
+        let mut buffer: [u8; 200] = [0; 200];
+        let result = block_on(run(&mut reader, |cache| {
+            runs += 1;
+            cache.read_at(100, &mut buffer)
+        }));
+        assert_eq!(result, Ok(()));
+        assert_eq!(buffer, data[100..300]);
+        assert_eq!(runs, 2);
+
+        // too big for the cache, it fails rather than retrying forever
+        let mut buffer = [0; BLOCK_SIZE * BLOCK_COUNT + 1];
+        let result = block_on(run(&mut reader, |cache| cache.read_at(0, &mut buffer)));
+        assert_eq!(result, Err(Error::BufferTooSmall));
+    }
+}
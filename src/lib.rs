@@ -3,10 +3,43 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(rustdoc::private_intra_doc_links)]
 
+mod charset;
 mod draw_target;
+#[cfg(feature = "embedded-io")]
+mod embedded_io;
+mod font_info;
+mod glyph_cache;
+mod glyph_names;
+#[cfg(feature = "embedded-io-async")]
+mod io_async;
+mod missing_glyph;
+#[cfg(feature = "embedded-storage")]
+mod nor_flash;
 mod parser;
+mod preload;
+mod properties;
+mod source;
 mod style;
+#[cfg(test)]
+mod test_utils;
 mod utils;
 
-pub use parser::{load_pcf_font, Error, PcfFont};
-pub use style::{PcfFontStyle, PcfFontStyleBuilder};
+pub use charset::Charset;
+#[cfg(feature = "embedded-io")]
+pub use embedded_io::EmbeddedIoSource;
+pub use font_info::{FontInfo, Slant, Xlfd};
+pub use glyph_cache::{GlyphCache, GlyphCacheSlot};
+#[cfg(feature = "embedded-io-async")]
+pub use io_async::{load_pcf_font_async, AsyncPcfFont};
+pub use missing_glyph::MissingGlyph;
+#[cfg(feature = "embedded-storage")]
+pub use nor_flash::NorFlashSource;
+pub use parser::{load_pcf_font, AcceleratorTable, DrawDirection, Error, MetricsEntry, PcfFont};
+pub use preload::PreloadedSource;
+pub use properties::{
+    Properties, Property, PropertyString, PropertyValue, MAX_PROPERTY_STRING_LENGTH,
+};
+#[cfg(feature = "std")]
+pub use source::IoSource;
+pub use source::{FontSource, OffsetSource};
+pub use style::{MeasureMode, PcfFontStyle, PcfFontStyleBuilder};
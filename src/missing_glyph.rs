@@ -0,0 +1,160 @@
+//! What's drawn for the characters missing from all the fonts of a style.
+
+use crate::MetricsEntry;
+
+/// How [crate::PcfFontStyle] handles characters missing from all its fonts.
+///
+/// Drawing and measuring use the same glyph, so the measured text always matches what's drawn.
+/// Replacement glyphs missing from the font take no space, like [MissingGlyph::Skip].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingGlyph {
+    /// The default character of the main font, from the font's encoding table.
+    #[default]
+    DefaultChar,
+    /// The glyph named `.notdef` in the main font.
+    Notdef,
+    /// Another character, looked up in all the fonts, e.g. `'?'` or `'\u{FFFD}'`.
+    Substitute(char),
+    /// A box showing the code point in hex digits, aka tofu.
+    ///
+    /// The digits need 13 pixels above the baseline, only the box is drawn for smaller fonts.
+    HexBox,
+    /// Nothing is drawn and the character takes no space.
+    Skip,
+}
+
+/// Height of a hex box with digits: 2 rows of 3x5 digits, a pixel apart, inside the border.
+const HEX_BOX_HEIGHT: usize = 13;
+
+/// Buffer size fitting the bitmap of any hex box, up to 15 pixels wide.
+pub(crate) const HEX_BOX_BYTES: usize = 2 * HEX_BOX_HEIGHT;
+
+/// 3x5 pixel hex digits, the 3 low bits of each row, leftmost pixel first.
+const HEX_DIGITS: [[u8; 5]; 16] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+    [0b111, 0b101, 0b111, 0b101, 0b101],
+    [0b110, 0b101, 0b110, 0b101, 0b110],
+    [0b111, 0b100, 0b100, 0b100, 0b111],
+    [0b110, 0b101, 0b101, 0b101, 0b110],
+    [0b111, 0b100, 0b111, 0b100, 0b111],
+    [0b111, 0b100, 0b111, 0b100, 0b100],
+];
+
+/// Metrics of the hex box of the code point, standing on the baseline.
+///
+/// The box is as tall as the digits, or `max_ascent` if that's smaller.
+pub(crate) fn hex_box_metrics(code_point: u32, max_ascent: i16) -> MetricsEntry {
+    // 4 digits in 2 columns, or 6 digits in 3 columns beyond the BMP
+    let columns = if code_point > 0xFFFF { 3 } else { 2 };
+    let width = columns * 4 + 3;
+    MetricsEntry {
+        left_side_bearing: 0,
+        right_side_bearing: width,
+        character_width: width + 1,
+        character_ascent: max_ascent.clamp(2, HEX_BOX_HEIGHT as i16),
+        character_descent: 0,
+        character_attributes: 0,
+    }
+}
+
+/// Draw the hex box of the code point into `buf`, rows padded to byte like
+/// [crate::PcfFont::read_glyph_raw], returns `(length, metrics)`.
+pub(crate) fn hex_box(
+    code_point: u32,
+    max_ascent: i16,
+    buf: &mut [u8; HEX_BOX_BYTES],
+) -> (usize, MetricsEntry) {
+    let metrics = hex_box_metrics(code_point, max_ascent);
+    let width = metrics.glyph_width() as usize;
+    let height = metrics.glyph_height() as usize;
+    let row_bytes = width.div_ceil(8);
+    let data = &mut buf[..height * row_bytes];
+    data.fill(0);
+    let mut set = |x: usize, y: usize| data[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+
+    for x in 0..width {
+        set(x, 0);
+        set(x, height - 1);
+    }
+    for y in 0..height {
+        set(0, y);
+        set(width - 1, y);
+    }
+    if height == HEX_BOX_HEIGHT {
+        let columns = (width - 3) / 4;
+        let digits = columns * 2;
+        for index in 0..digits {
+            let digit = (code_point >> (4 * (digits - 1 - index))) & 0xF;
+            let left = 2 + index % columns * 4;
+            let top = 1 + index / columns * 6;
+            for (y, row) in HEX_DIGITS[digit as usize].iter().enumerate() {
+                for x in 0..3 {
+                    if row & (0b100 >> x) != 0 {
+                        set(left + x, top + y);
+                    }
+                }
+            }
+        }
+    }
+
+    (height * row_bytes, metrics)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn drawing_hex_boxes() {
+        let mut buf = [0; HEX_BOX_BYTES];
+        let (length, metrics) = hex_box(0x4E16, 14, &mut buf);
+        assert_eq!(metrics, hex_box_metrics(0x4E16, 14));
+        assert_eq!(metrics.character_width, 12);
+        let expected = [
+            "###########",
+            "#.#.#.###.#",
+            "#.#.#.#...#",
+            "#.###.###.#",
+            "#...#.#...#",
+            "#...#.###.#",
+            "#.........#",
+            "#..#..###.#",
+            "#.##..#...#",
+            "#..#..###.#",
+            "#..#..#.#.#",
+            "#.###.###.#",
+            "###########",
+        ];
+        assert_eq!(length, expected.len() * 2);
+        for (y, row) in expected.iter().enumerate() {
+            let drawn: [u8; 11] =
+                core::array::from_fn(|x| match buf[y * 2 + x / 8] & (0x80 >> (x % 8)) {
+                    0 => b'.',
+                    _ => b'#',
+                });
+            assert_eq!(&drawn, row.as_bytes(), "row {y}");
+        }
+
+        // 6 digits beyond the BMP
+        assert_eq!(hex_box_metrics(0x1F600, 14).glyph_width(), 15);
+        // too small for the digits, only the box
+        let (length, metrics) = hex_box(0x4E16, 8, &mut buf);
+        assert_eq!(metrics.glyph_height(), 8);
+        assert_eq!(
+            &buf[..length],
+            &[
+                0xFF, 0xE0, 0x80, 0x20, 0x80, 0x20, 0x80, 0x20, 0x80, 0x20, 0x80, 0x20, 0x80, 0x20,
+                0xFF, 0xE0
+            ]
+        );
+    }
+}
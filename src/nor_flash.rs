@@ -0,0 +1,183 @@
+//! NOR flash font source, enabled by the `embedded-storage` feature.
+//!
+//! Fonts too big for the RAM can stay in an external NOR flash, glyphs are read when drawing.
+
+use core::cell::RefCell;
+
+use embedded_storage::nor_flash::ReadNorFlash;
+
+use crate::{Error, FontSource};
+
+/// A font stored in a NOR flash, starting at address 0.
+///
+/// All the reads are aligned to [ReadNorFlash::READ_SIZE], unaligned data is read through a
+/// scratch buffer of `SCRATCH_SIZE` bytes on the stack, which must hold at least one read. Use
+/// [crate::OffsetSource] if the font is stored somewhere else in the flash.
+pub struct NorFlashSource<F, const SCRATCH_SIZE: usize = 64> {
+    flash: RefCell<F>,
+}
+
+impl<F> NorFlashSource<F>
+where
+    F: ReadNorFlash,
+{
+    /// Read through a 64 bytes scratch buffer, for flashes with a `READ_SIZE` up to 64.
+    pub fn new(flash: F) -> Self {
+        Self::with_scratch_size(flash)
+    }
+}
+
+impl<F, const SCRATCH_SIZE: usize> NorFlashSource<F, SCRATCH_SIZE>
+where
+    F: ReadNorFlash,
+{
+    /// Same with [NorFlashSource::new], but with another scratch buffer size, e.g.
+    /// `NorFlashSource::<_, 256>::with_scratch_size(flash)` for a 256 bytes `READ_SIZE`.
+    pub fn with_scratch_size(flash: F) -> Self {
+        const {
+            assert!(
+                SCRATCH_SIZE >= F::READ_SIZE && SCRATCH_SIZE > 0,
+                "the scratch buffer must hold at least one read"
+            )
+        };
+        Self {
+            flash: RefCell::new(flash),
+        }
+    }
+
+    /// Gets the flash back.
+    pub fn into_inner(self) -> F {
+        self.flash.into_inner()
+    }
+}
+
+impl<F, const SCRATCH_SIZE: usize> FontSource for NorFlashSource<F, SCRATCH_SIZE>
+where
+    F: ReadNorFlash,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let read_size = F::READ_SIZE.max(1);
+        // the biggest aligned read fitting in the scratch buffer, never 0 as checked in the
+        // constructor
+        let scratch_length = SCRATCH_SIZE - SCRATCH_SIZE % read_size;
+        let mut flash = self.flash.try_borrow_mut().map_err(|_| Error::Other)?;
+        let capacity = flash.capacity();
+        let mut position = usize::try_from(offset).map_err(|_| Error::Io)?;
+        if position
+            .checked_add(buf.len())
+            .is_none_or(|end| end > capacity)
+        {
+            return Err(Error::Io);
+        }
+        let mut read = |address: usize, bytes: &mut [u8]| {
+            let address = u32::try_from(address).map_err(|_| Error::Io)?;
+            flash.read(address, bytes).map_err(|_| Error::Io)
+        };
+        let mut done = 0;
+        while done < buf.len() {
+            let remaining = buf.len() - done;
+            let skip = position % read_size;
+            if skip == 0 && remaining >= read_size {
+                // aligned part, read in place
+                let length = remaining - remaining % read_size;
+                read(position, &mut buf[done..done + length])?;
+                done += length;
+                position += length;
+                continue;
+            }
+            // unaligned head or tail, the read may not go beyond the flash
+            let mut scratch: [u8; SCRATCH_SIZE] = [0; SCRATCH_SIZE];
+            let length = (skip + remaining)
+                .next_multiple_of(read_size)
+                .min(scratch_length)
+                .min(capacity - (position - skip));
+            read(position - skip, &mut scratch[..length])?;
+            let count = (length - skip).min(remaining);
+            buf[done..done + count].copy_from_slice(&scratch[skip..skip + count]);
+            done += count;
+            position += count;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_storage::nor_flash::{ErrorType, NorFlashErrorKind};
+
+    use crate::{load_pcf_font, test_utils::FONT_LATIN1_LSBYTE};
+
+    use super::*;
+
+    /// Flash checking the reads are aligned and within the flash.
+    struct AlignedFlash<const READ_SIZE: usize> {
+        data: &'static [u8],
+    }
+
+    impl<const READ_SIZE: usize> ErrorType for AlignedFlash<READ_SIZE> {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl<const READ_SIZE: usize> ReadNorFlash for AlignedFlash<READ_SIZE> {
+        const READ_SIZE: usize = READ_SIZE;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            let offset = offset as usize;
+            assert_eq!(offset % READ_SIZE, 0);
+            assert_eq!(bytes.len() % READ_SIZE, 0);
+            assert!(offset + bytes.len() <= self.capacity());
+            // the flash is erased beyond the data
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = self.data.get(offset + i).copied().unwrap_or(0xFF);
+            }
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.data.len().next_multiple_of(READ_SIZE)
+        }
+    }
+
+    fn assert_same_data(source: &impl FontSource) {
+        let mut buffer: [u8; 150] = [0; 150];
+        for offset in [0, 1, 3, 4, 5, 63, 64, 65, 1000] {
+            for length in [0, 1, 2, 3, 4, 7, 63, 64, 65, 150] {
+                source.read_at(offset, &mut buffer[..length]).unwrap();
+                let offset = offset as usize;
+                assert_eq!(
+                    buffer[..length],
+                    FONT_LATIN1_LSBYTE[offset..offset + length],
+                    "reading {length} bytes at {offset}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reading_nor_flash() {
+        let data = FONT_LATIN1_LSBYTE;
+        assert_same_data(&NorFlashSource::new(AlignedFlash::<1> { data }));
+        assert_same_data(&NorFlashSource::new(AlignedFlash::<4> { data }));
+        assert_same_data(&NorFlashSource::new(AlignedFlash::<16> { data }));
+        let flash = AlignedFlash::<128> { data };
+        assert_same_data(&NorFlashSource::<_, 128>::with_scratch_size(flash));
+
+        // nothing is read beyond the flash
+        let source = NorFlashSource::new(AlignedFlash::<16> { data });
+        let capacity = data.len().next_multiple_of(16) as u64;
+        assert_eq!(source.read_at(capacity - 3, &mut [0; 3]), Ok(()));
+        assert_eq!(source.read_at(capacity - 3, &mut [0; 4]), Err(Error::Io));
+
+        let font = load_pcf_font(NorFlashSource::new(AlignedFlash::<4> { data })).unwrap();
+        let expected = load_pcf_font(data).unwrap();
+        let mut buffer: [u8; 32] = [0; 32];
+        let mut expected_buffer: [u8; 32] = [0; 32];
+        for c in "Aé~".chars() {
+            assert_eq!(
+                font.read_glyph_raw(c as u32, &mut buffer),
+                expected.read_glyph_raw(c as u32, &mut expected_buffer)
+            );
+            assert_eq!(buffer, expected_buffer);
+        }
+    }
+}
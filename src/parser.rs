@@ -9,7 +9,7 @@
 //! This lib only aims to read the glyphs in PCF fonts and interface with embedded-graphics.
 //! Not all features are implemented.
 //!
-//! The properties table is dynamic, and may be implemented as an iterator.
+//! The properties table is dynamic, and is read on demand through an iterator, see [crate::properties].
 //!
 //! The metrics table stores per-glyph metric data.
 //!
@@ -17,24 +17,32 @@
 //!
 //! PCF only supports 1 or 2 bytes encoding.
 //!
-//! TODO: `no_std` io::Seek and io::Read.
+//! The font data is read through [FontSource], so `no_std` targets can use a byte slice.
 
 use core::fmt::Debug;
 use num_enum::FromPrimitive;
 #[cfg(feature = "std")]
 use std::io;
 
-use crate::utils::*;
+use crate::{
+    charset::Charset,
+    glyph_names::GlyphNamesTable,
+    properties::PropertiesTable,
+    source::{FontSource, SourceReader},
+    utils::*,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[non_exhaustive]
 pub enum Error {
-    /// This lib only supports MSByte first and MSBit first encoding for those fields with multiple possible encodings.
+    /// The data is not a PCF font, or uses a format variant not supported yet.
     UnsupportedFormat,
     /// The data is not organized in an expected way, possibly currupted.
     CorruptedData,
-    /// This error is raised when the code point is not found.
+    /// This error is raised when the code point, glyph or table is not found.
     NotFound,
+    /// The buffer provided is too small to hold the data.
+    BufferTooSmall,
     /// Something's wrong with IO operations.
     /// In some cases, currupted data also leads to IO error.
     Io,
@@ -42,6 +50,7 @@ pub enum Error {
     Other,
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for Error {
     fn from(value: io::Error) -> Self {
         let _ = value;
@@ -82,7 +91,7 @@ const PCF_COMPRESSED_METRICS: u32 = 0x00000100;
 // Table format extra flags
 /// how each row in each glyph's bitmap is padded (format&3)
 ///  0=>bytes, 1=>shorts, 2=>ints
-const PCF_GLYPH_PAD_MASK: u32 = 3 << 0;
+const PCF_GLYPH_PAD_MASK: u32 = 3;
 /// If set then Most Sig Byte First
 const PCF_BYTE_MASK: u32 = 1 << 2;
 /// If set then Most Sig Bit First
@@ -91,22 +100,89 @@ const PCF_BIT_MASK: u32 = 1 << 3;
 /// 0=>bytes, 1=>shorts, 2=>ints
 const PCF_SCAN_UNIT_MASK: u32 = 3 << 4;
 
+/// The encoding table's default character when the font has none.
+const NO_DEFAULT_CHAR: u16 = 0xFFFF;
+
 /// Returns the length of each row in bytes.
 const fn bytes_per_row(width: usize, bytes_align: usize) -> usize {
     let unit_align_bits = bytes_align * 8;
-    // div floor
-    let block_count = (width + unit_align_bits - 1) / unit_align_bits;
+    let block_count = width.div_ceil(unit_align_bits);
     block_count * bytes_align
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Clone, Copy)]
-struct TableTocEntry {
+pub(crate) struct TableTocEntry {
     format: u32,
     size: u32,
     offset: u32,
 }
 
+impl TableTocEntry {
+    /// The byte order of all the fields in the table except the leading format field,
+    /// which is always LSByte first.
+    #[inline]
+    pub(crate) fn byte_order(&self) -> ByteOrder {
+        if self.format & PCF_BYTE_MASK > 0 {
+            ByteOrder::MsbFirst
+        } else {
+            ByteOrder::LsbFirst
+        }
+    }
+
+    /// Gets the absolute offset of `length` bytes at `relative_offset` in the table.
+    ///
+    /// Returns [Error::CorruptedData] if they don't fit in the table.
+    #[inline]
+    pub(crate) fn locate(&self, relative_offset: u64, length: u64) -> Result<u64, Error> {
+        match relative_offset.checked_add(length) {
+            Some(end) if end <= self.size as u64 => Ok(self.offset as u64 + relative_offset),
+            _ => Err(Error::CorruptedData),
+        }
+    }
+}
+
+/// Location and format of a metrics table, used by both the metrics and the ink metrics tables.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MetricsTable {
+    /// Whether metrics are compressed.
+    compressed: bool,
+    byte_order: ByteOrder,
+    /// The absolute offset to the first metric entry.
+    ///
+    /// Use glyph index against this to get the glyph metrics
+    data_location: u64,
+}
+
+impl MetricsTable {
+    /// Load the metrics table header, returns the table and its metrics count.
+    fn load<T>(cursor: &mut SourceReader<'_, T>, toc: TableTocEntry) -> Result<(Self, u32), Error>
+    where
+        T: FontSource + ?Sized,
+    {
+        let mut buffer: [u8; 8] = [0; 8];
+        cursor.seek(toc.locate(0, 8)?);
+        cursor.read_exact(&mut buffer)?;
+        let compressed = toc.format & PCF_COMPRESSED_METRICS > 0;
+        let byte_order = toc.byte_order();
+        let (count, header_size, entry_size) = if compressed {
+            (
+                byte_order.u16_from_bytes_ref(&buffer[4..6]) as u32,
+                4 + 2,
+                5,
+            )
+        } else {
+            (byte_order.u32_from_bytes_ref(&buffer[4..8]), 4 + 4, 12)
+        };
+        let data_location = toc.locate(header_size, count as u64 * entry_size)?;
+        let table = Self {
+            compressed,
+            byte_order,
+            data_location,
+        };
+        Ok((table, count))
+    }
+}
+
 /// Uncompressed metrics data
 ///
 /// All glyphs in PCF uses ascent & descent to describe the glyphs Y cordinate.
@@ -146,29 +222,77 @@ impl MetricsEntry {
     /// Deserialize uncompressed data to create a [`MetricsEntry`]
     ///
     /// No boundary checking. The data length should be at least 12.
-    fn new_from_standard(data: &[u8]) -> Self {
+    fn new_from_standard(data: &[u8], byte_order: ByteOrder) -> Self {
         Self {
-            left_side_bearing: i16_from_be_bytes_ref(&data[0..=2]),
-            right_side_bearing: i16_from_be_bytes_ref(&data[2..=4]),
-            character_width: i16_from_be_bytes_ref(&data[4..=6]),
-            character_ascent: i16_from_be_bytes_ref(&data[6..=8]),
-            character_descent: i16_from_be_bytes_ref(&data[8..=10]),
-            character_attributes: u16_from_be_bytes_ref(&data[10..=12]),
+            left_side_bearing: byte_order.i16_from_bytes_ref(&data[0..2]),
+            right_side_bearing: byte_order.i16_from_bytes_ref(&data[2..4]),
+            character_width: byte_order.i16_from_bytes_ref(&data[4..6]),
+            character_ascent: byte_order.i16_from_bytes_ref(&data[6..8]),
+            character_descent: byte_order.i16_from_bytes_ref(&data[8..10]),
+            character_attributes: byte_order.u16_from_bytes_ref(&data[10..12]),
         }
     }
 
+    /// The x-axis of the first visible pixel, relative to the origin.
+    #[inline]
+    pub fn left_side_bearing(&self) -> i16 {
+        self.left_side_bearing
+    }
+
+    /// The x-axis after the last visible pixel, relative to the origin.
+    #[inline]
+    pub fn right_side_bearing(&self) -> i16 {
+        self.right_side_bearing
+    }
+
+    /// The distance from the origin to the origin of the next character, aka advance width.
+    #[inline]
+    pub fn character_width(&self) -> i16 {
+        self.character_width
+    }
+
+    /// The number of pixels above the baseline.
+    #[inline]
+    pub fn character_ascent(&self) -> i16 {
+        self.character_ascent
+    }
+
+    /// The number of pixels below the baseline.
+    #[inline]
+    pub fn character_descent(&self) -> i16 {
+        self.character_descent
+    }
+
+    #[inline]
+    pub fn character_attributes(&self) -> u16 {
+        self.character_attributes
+    }
+
     /// Gets the width of the glyph.
     ///
-    /// Note this is different from the field [MetricsEntry::character_width].
+    /// Note this is different from [MetricsEntry::character_width].
     #[inline]
     pub fn glyph_width(&self) -> i16 {
-        self.right_side_bearing - self.left_side_bearing
+        self.right_side_bearing
+            .saturating_sub(self.left_side_bearing)
     }
 
     /// Gets the height of the glyph.
     #[inline]
     pub fn glyph_height(&self) -> i16 {
-        self.character_ascent + self.character_descent
+        self.character_ascent.saturating_add(self.character_descent)
+    }
+
+    /// Gets the glyph `(width, height)`, checking they are in `0..=i16::MAX`.
+    fn checked_glyph_size(&self) -> Result<(usize, usize), Error> {
+        let width = self.right_side_bearing as i32 - self.left_side_bearing as i32;
+        let height = self.character_ascent as i32 + self.character_descent as i32;
+        let range = 0..=i16::MAX as i32;
+        if range.contains(&width) && range.contains(&height) {
+            Ok((width as usize, height as usize))
+        } else {
+            Err(Error::CorruptedData)
+        }
     }
 }
 
@@ -180,26 +304,96 @@ impl MetricsEntry {
 /// they will have some extra metrics data at the end.
 ///
 /// The format field is ommitted as it just encoded font state.
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy)]
-struct AcceleratorTable {
-    no_overlap: u8,
-    constant_metrics: u8,
-    terminal_font: u8,
-    constant_width: u8,
-    ink_inside: u8,
-    ink_metrics: u8,
-    draw_direction: u8,
-    padding: u8, // padding?
-    font_ascent: i32,
-    font_descent: i32,
-    max_overlap: i32, // not used but takes some space
-    minbounds: MetricsEntry,
-    maxbounds: MetricsEntry,
-    /// Exists if format is PCF_ACCEL_W_INKBOUNDS otherwise `should` be same with minbounds,
-    ink_minbounds: Option<MetricsEntry>,
-    /// Exists if format is PCF_ACCEL_W_INKBOUNDS otherwise `should` be same with maxbounds,
-    ink_maxbounds: Option<MetricsEntry>,
+///
+/// Use [PcfFont::accelerators] to get it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct AcceleratorTable {
+    /// If for all glyphs `left_side_bearing - character_width` is less than or equal to
+    /// `max_overlap`, glyphs never overlap with the next one.
+    pub no_overlap: bool,
+    /// All glyphs have the same metrics.
+    pub constant_metrics: bool,
+    /// `constant_metrics`, and every glyph has `left_side_bearing == 0`,
+    /// `right_side_bearing == character_width`, `ascent == font_ascent` and
+    /// `descent == font_descent`, so each glyph fully covers its character cell.
+    pub terminal_font: bool,
+    /// All glyphs have the same `character_width`.
+    pub constant_width: bool,
+    /// All the lit pixels of a glyph are inside its character cell.
+    pub ink_inside: bool,
+    /// Ink metrics are different from the metrics of some glyphs.
+    pub ink_metrics: bool,
+    pub draw_direction: DrawDirection,
+    pub font_ascent: i32,
+    pub font_descent: i32,
+    pub max_overlap: i32,
+    pub minbounds: MetricsEntry,
+    pub maxbounds: MetricsEntry,
+    /// Same with [AcceleratorTable::minbounds] if the table comes without ink bounds.
+    pub ink_minbounds: MetricsEntry,
+    /// Same with [AcceleratorTable::maxbounds] if the table comes without ink bounds.
+    pub ink_maxbounds: MetricsEntry,
+}
+
+impl AcceleratorTable {
+    /// Load the accelerator table described by `toc`.
+    fn load<T>(cursor: &mut SourceReader<'_, T>, toc: TableTocEntry) -> Result<Self, Error>
+    where
+        T: FontSource + ?Sized,
+    {
+        let byte_order = toc.byte_order();
+        let with_ink_bounds = toc.format & PCF_ACCEL_W_INKBOUNDS > 0;
+        let bounds_count = if with_ink_bounds { 4 } else { 2 };
+        let mut buffer: [u8; 20] = [0; 20];
+        // skip format field
+        cursor.seek(toc.locate(4, 20 + bounds_count * 12)?);
+        cursor.read_exact(&mut buffer[0..20])?;
+        let flags = &buffer[0..8];
+        let font_ascent = byte_order.i32_from_bytes_ref(&buffer[8..12]);
+        let font_descent = byte_order.i32_from_bytes_ref(&buffer[12..16]);
+        let max_overlap = byte_order.i32_from_bytes_ref(&buffer[16..20]);
+        let read_metrics = |cursor: &mut SourceReader<'_, T>| -> Result<MetricsEntry, Error> {
+            let mut buffer: [u8; 12] = [0; 12];
+            cursor.read_exact(&mut buffer)?;
+            Ok(MetricsEntry::new_from_standard(&buffer, byte_order))
+        };
+        let minbounds = read_metrics(cursor)?;
+        let maxbounds = read_metrics(cursor)?;
+        let (ink_minbounds, ink_maxbounds) = if with_ink_bounds {
+            (read_metrics(cursor)?, read_metrics(cursor)?)
+        } else {
+            (minbounds, maxbounds)
+        };
+        Ok(Self {
+            no_overlap: flags[0] != 0,
+            constant_metrics: flags[1] != 0,
+            terminal_font: flags[2] != 0,
+            constant_width: flags[3] != 0,
+            ink_inside: flags[4] != 0,
+            ink_metrics: flags[5] != 0,
+            draw_direction: if flags[6] == 0 {
+                DrawDirection::LeftToRight
+            } else {
+                DrawDirection::RightToLeft
+            },
+            // flags[7] is padding
+            font_ascent,
+            font_descent,
+            max_overlap,
+            minbounds,
+            maxbounds,
+            ink_minbounds,
+            ink_maxbounds,
+        })
+    }
+}
+
+/// The direction glyphs are drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawDirection {
+    LeftToRight,
+    RightToLeft,
 }
 
 #[allow(dead_code)]
@@ -241,44 +435,65 @@ pub struct BoundingBox {
 
 /// The PCF font container.
 ///
-/// Users should use [load_pcf_font] to load a font from a readable & seekable object.
+/// Users should use [load_pcf_font] to load a font from a [FontSource], e.g. `&[u8]`.
 #[derive(PartialEq, Clone)]
 #[non_exhaustive]
 pub struct PcfFont<T> {
-    data_cursor: T,
+    pub(crate) source: T,
     /// Glyph count, informative. Original value is signed.
     glyph_count: u32,
     /// The number of pixels above the baseline of a typical ascender
     ascent: i32,
     /// The number of pixels below the baseline of a typical descender
     descent: i32,
-    /// Whether metrics are compressed.
-    metrics_compressed: bool,
+    /// The metrics table
+    metrics_table: MetricsTable,
+    /// The ink metrics table, optional
+    ink_metrics_table: Option<MetricsTable>,
+    /// Byte order and the absolute offset to the first scalable width, optional
+    ///
+    /// Use glyph index against this to get the scalable width.
+    swidths_location: Option<(ByteOrder, u64)>,
+    /// Byte order of the bitmap table
+    bitmaps_byte_order: ByteOrder,
+    /// Byte order of the encoding table
+    encodings_byte_order: ByteOrder,
+    /// Whether the leftmost pixel of each glyph byte is its most significant bit
+    bitmaps_msbit_first: bool,
+    /// Size in bytes of the units glyph bits are stored in, 1, 2 or 4
+    bitmaps_scan_unit: usize,
     /// The maximum glyph size as a 4-tuple of: width, height, x_offset, y_offset
     pub(crate) bounding_box: BoundingBox,
+    /// Font-wide information
+    accelerators: AcceleratorTable,
 
     glyph_row_padding_format: GlyphPaddingFormat,
     // the 4 fields below actually only contains data of u8 size.
-    min_char_or_byte2: u16,       /* As in XFontStruct */
-    max_char_or_byte2: u16,       /* As in XFontStruct */
-    min_byte1: u16,               /* As in XFontStruct */
-    max_byte1: u16,               /* As in XFontStruct */
-    pub(crate) default_char: u16, /* As in XFontStruct */
+    min_char_or_byte2: u16, /* As in XFontStruct */
+    max_char_or_byte2: u16, /* As in XFontStruct */
+    min_byte1: u16,         /* As in XFontStruct */
+    max_byte1: u16,         /* As in XFontStruct */
+    /// `None` if the font has no default character, or it has no glyph
+    default_char: Option<u16>,
 
     /// Use data here to get the glyph index of a code point.
-    encoded_glyph_indices_location: u32,
+    encoded_glyph_indices_location: u64,
     /// The absolute offset to bitmap offsets look up table in bitmap table
     ///
     /// Use glyph index against this to get the bitmap position.
-    bitmap_position_lut_location: u32,
+    bitmap_position_lut_location: u64,
     /// The absolute offset to the first bitmap data in bitmap table
     ///
     /// Use bitmap position against this to get the glyph data.
-    bitmap_data_location: u32,
-    /// The absolute offset to the first metric entry in metrics table.
-    ///
-    /// Use glyph index against this to get the glyph metrics
-    metrics_data_location: u32,
+    bitmap_data_location: u64,
+    /// The size of the bitmap data in bitmap table
+    bitmap_data_size: u64,
+    /// The properties table, optional
+    pub(crate) properties_table: Option<PropertiesTable>,
+    /// The glyph names table, optional
+    pub(crate) glyph_names_table: Option<GlyphNamesTable>,
+    /// Encoding of the code points, Unicode is translated into it
+    charset: Charset,
 }
 
 impl<T> PcfFont<T> {
@@ -288,6 +503,13 @@ impl<T> PcfFont<T> {
         self.bounding_box
     }
 
+    /// Font-wide flags and bounds, from the BDF accelerators table if present, otherwise from the
+    /// accelerators table.
+    #[inline]
+    pub fn accelerators(&self) -> AcceleratorTable {
+        self.accelerators
+    }
+
     #[inline]
     pub fn glyph_count(&self) -> u32 {
         self.glyph_count
@@ -312,84 +534,644 @@ impl<T> PcfFont<T> {
 
     #[inline]
     pub fn max_bytes_per_glyph(&self) -> usize {
+        // never negative, checked when loading
         let width = self.bounding_box.width as usize;
         let height = self.bounding_box.height as usize;
         let row_bytes = bytes_per_row(width, 1);
         height * row_bytes
     }
 
-    /// Override the default character.
+    /// The default character in the font's encoding, if the font has one with a glyph or it's
+    /// overridden by [PcfFont::override_default_char].
+    ///
+    /// It's drawn for missing characters by default, see [crate::MissingGlyph::DefaultChar].
+    #[inline]
+    pub fn default_char(&self) -> Option<u16> {
+        self.default_char
+    }
+
+    /// Override the default character, in the font's encoding. `0xFFFF` means no default
+    /// character, like in the encoding table.
     ///
-    /// Whether this field is used depends on the implementation.
+    /// The value isn't checked, a default character without glyph is just never drawn. Use
+    /// [PcfFont::try_override_default_char] to check it.
     #[inline]
     pub fn override_default_char(&mut self, value: u16) {
-        self.default_char = value;
+        self.default_char = (value != NO_DEFAULT_CHAR).then_some(value);
+    }
+
+    /// Encoding of the font's code points, detected from the `CHARSET_REGISTRY` and
+    /// `CHARSET_ENCODING` properties when loading.
+    ///
+    /// Code points given to the font are always Unicode, they are translated into this charset to
+    /// look up glyphs.
+    #[inline]
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+
+    /// Override the charset, e.g. for fonts without the charset properties. Use [Charset::Unknown]
+    /// to look up glyphs with code points in the font's encoding.
+    #[inline]
+    pub fn override_charset(&mut self, charset: Charset) {
+        self.charset = charset;
+    }
+
+    /// `(absolute offset, length)` of the tables used to look up glyphs: the encoding indices, the
+    /// bitmap offsets, the metrics and the ink metrics, which may be empty.
+    pub(crate) fn index_tables(&self) -> [(u64, usize); 4] {
+        // the ranges are within a byte and the tables fit in the data, checked when loading
+        let encoded_glyph_count = (self.max_char_or_byte2 - self.min_char_or_byte2 + 1) as usize
+            * (self.max_byte1 - self.min_byte1 + 1) as usize;
+        let glyph_count = self.glyph_count as usize;
+        let metrics = |table: MetricsTable| {
+            let entry_size = if table.compressed { 5 } else { 12 };
+            (table.data_location, glyph_count * entry_size)
+        };
+        [
+            (self.encoded_glyph_indices_location, encoded_glyph_count * 2),
+            (self.bitmap_position_lut_location, glyph_count * 4),
+            metrics(self.metrics_table),
+            self.ink_metrics_table.map(metrics).unwrap_or((0, 0)),
+        ]
+    }
+
+    /// The same font read from another source.
+    pub(crate) fn with_source<S>(&self, source: S) -> PcfFont<S> {
+        PcfFont {
+            source,
+            glyph_count: self.glyph_count,
+            ascent: self.ascent,
+            descent: self.descent,
+            metrics_table: self.metrics_table,
+            ink_metrics_table: self.ink_metrics_table,
+            swidths_location: self.swidths_location,
+            bitmaps_byte_order: self.bitmaps_byte_order,
+            encodings_byte_order: self.encodings_byte_order,
+            bitmaps_msbit_first: self.bitmaps_msbit_first,
+            bitmaps_scan_unit: self.bitmaps_scan_unit,
+            bounding_box: self.bounding_box,
+            accelerators: self.accelerators,
+            glyph_row_padding_format: self.glyph_row_padding_format,
+            min_char_or_byte2: self.min_char_or_byte2,
+            max_char_or_byte2: self.max_char_or_byte2,
+            min_byte1: self.min_byte1,
+            max_byte1: self.max_byte1,
+            default_char: self.default_char,
+            encoded_glyph_indices_location: self.encoded_glyph_indices_location,
+            bitmap_position_lut_location: self.bitmap_position_lut_location,
+            bitmap_data_location: self.bitmap_data_location,
+            bitmap_data_size: self.bitmap_data_size,
+            properties_table: self.properties_table,
+            glyph_names_table: self.glyph_names_table,
+            charset: self.charset,
+        }
     }
 }
 
 impl<T> PcfFont<T>
 where
-    T: io::Read + io::Seek + Clone,
+    T: FontSource,
 {
     /// Read raw glyph data of the given code_point, return `(length, metrics)`
     /// where `length` is the length of data written, the `metrics` is the glyph's metrics to help
     /// displaying the glyph.
     ///
+    /// Code points are `u32` so a `char` converts without loss, e.g. `c as u32`. Those the font
+    /// can't encode, like anything beyond U+FFFF, get [Error::NotFound].
+    ///
     /// Glyph rows are always padded to bytes.
     ///
-    /// There might be arbitrary glyph sizes. Use the bounding box or [PcfFont::max_bytes_per_glyph
+    /// There might be arbitrary glyph sizes. Use the bounding box or [PcfFont::max_bytes_per_glyph]
     /// to calculate the maximum required buffer size.
     ///
     /// In some cases the glyph will be empty, while it still needs space when displaying it.
+    ///
+    /// Returns [Error::BufferTooSmall] if `buf` can't hold the glyph.
     pub fn read_glyph_raw(
         &self,
-        code_point: u16,
+        code_point: u32,
         buf: &mut [u8],
     ) -> Result<(usize, MetricsEntry), Error> {
-        let mut cursor = self.data_cursor.clone();
+        let mut cursor = SourceReader::new(&self.source);
         let glyph_index = self.get_glyph_index(&mut cursor, code_point)?;
-        let bitmap_offset = self.get_glyph_bitmap_offset(&mut cursor, glyph_index)?;
+        self.read_glyph_raw_at(&mut cursor, glyph_index, buf)
+    }
+
+    /// Same with [PcfFont::read_glyph_raw], but use the internal glyph index to locate the glyph.
+    ///
+    /// This makes glyphs without a code point(e.g. `.notdef`) usable, see
+    /// [PcfFont::glyph_index_by_name].
+    pub fn read_glyph_raw_by_index(
+        &self,
+        glyph_index: u16,
+        buf: &mut [u8],
+    ) -> Result<(usize, MetricsEntry), Error> {
+        if glyph_index as u32 >= self.glyph_count {
+            return Err(Error::NotFound);
+        }
+        let mut cursor = SourceReader::new(&self.source);
+        self.read_glyph_raw_at(&mut cursor, glyph_index, buf)
+    }
+
+    /// Same with [PcfFont::read_glyph_raw], but read only the rows from `first_row` fitting in
+    /// `buf`, so glyphs of any size can be handled with a small buffer.
+    ///
+    /// The number of rows read is `length / bytes_per_row`, where `bytes_per_row` is
+    /// `(metrics.glyph_width() + 7) / 8`. The length is 0 once `first_row` reaches the glyph
+    /// height.
+    ///
+    /// Returns [Error::BufferTooSmall] if `buf` can't hold a single row.
+    pub fn read_glyph_rows(
+        &self,
+        code_point: u32,
+        first_row: usize,
+        buf: &mut [u8],
+    ) -> Result<(usize, MetricsEntry), Error> {
+        let mut cursor = SourceReader::new(&self.source);
+        let glyph_index = self.get_glyph_index(&mut cursor, code_point)?;
+        self.read_glyph_rows_at(&mut cursor, glyph_index, first_row, buf)
+    }
+
+    /// Same with [PcfFont::read_glyph_rows], but use the internal glyph index to locate the glyph.
+    pub fn read_glyph_rows_by_index(
+        &self,
+        glyph_index: u16,
+        first_row: usize,
+        buf: &mut [u8],
+    ) -> Result<(usize, MetricsEntry), Error> {
+        if glyph_index as u32 >= self.glyph_count {
+            return Err(Error::NotFound);
+        }
+        let mut cursor = SourceReader::new(&self.source);
+        self.read_glyph_rows_at(&mut cursor, glyph_index, first_row, buf)
+    }
+
+    fn read_glyph_rows_at(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+        first_row: usize,
+        buf: &mut [u8],
+    ) -> Result<(usize, MetricsEntry), Error> {
+        let metrics = self.get_metrics(cursor, glyph_index)?;
+        let (glyph_width, glyph_height) = metrics.checked_glyph_size()?;
+        let standard_row_bytes = bytes_per_row(glyph_width, 1);
+        let remaining_rows = glyph_height.saturating_sub(first_row);
+        let rows = match buf.len().checked_div(standard_row_bytes) {
+            Some(0) if remaining_rows > 0 => return Err(Error::BufferTooSmall),
+            Some(fitting_rows) => remaining_rows.min(fitting_rows),
+            // empty rows, nothing to read
+            None => 0,
+        };
+        let length = rows * standard_row_bytes;
+        self.read_rows(cursor, glyph_index, metrics, first_row, &mut buf[..length])?;
+        Ok((length, metrics))
+    }
+
+    fn read_glyph_raw_at(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+        buf: &mut [u8],
+    ) -> Result<(usize, MetricsEntry), Error> {
+        let metrics = self.get_metrics(cursor, glyph_index)?;
+        let (glyph_width, glyph_height) = metrics.checked_glyph_size()?;
+        // convert all padding scheme to padding to bytes
+        let length = glyph_height * bytes_per_row(glyph_width, 1);
+        if buf.len() < length {
+            return Err(Error::BufferTooSmall);
+        }
+        self.read_rows(cursor, glyph_index, metrics, 0, &mut buf[..length])?;
+        // the length of data written, the width of the bitmap
+        Ok((length, metrics))
+    }
+
+    /// Fill `buf` with the glyph rows from `first_row`, padded to byte.
+    ///
+    /// The length of `buf` must be a multiple of the row length and not go beyond the glyph.
+    fn read_rows(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+        metrics: MetricsEntry,
+        first_row: usize,
+        buf: &mut [u8],
+    ) -> Result<(), Error> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let (glyph_width, glyph_height) = metrics.checked_glyph_size()?;
+        let location =
+            self.glyph_bitmap_location(cursor, glyph_index, glyph_width, glyph_height)?;
+        let stored_row_bytes = self.original_row_bytes(glyph_width);
+        let row_bytes = bytes_per_row(glyph_width, 1);
+        let bitmap = (location, stored_row_bytes);
+        self.read_tile(cursor, bitmap, (first_row, 0), row_bytes, buf)
+    }
+
+    /// Same with [PcfFont::read_glyph_rows_by_index], but read only the bytes from `first_byte`
+    /// of each row, so glyph rows of any length can be handled with a small buffer.
+    ///
+    /// `bitmap` comes from [PcfFont::glyph_bitmap_by_index], so the metrics and the location of
+    /// the glyph are read once for all its tiles.
+    ///
+    /// Returns `(length, tile_row_bytes)`, the tile is as wide as the rest of the rows or `buf`,
+    /// and as high as fits in `buf`. The length is 0 once `first_row` reaches the glyph height or
+    /// `first_byte` the row length.
+    pub(crate) fn read_glyph_tile(
+        &self,
+        (location, stored_row_bytes, metrics): (u64, usize, MetricsEntry),
+        first_row: usize,
+        first_byte: usize,
+        buf: &mut [u8],
+    ) -> Result<(usize, usize), Error> {
+        let (glyph_width, glyph_height) = metrics.checked_glyph_size()?;
+        let remaining_rows = glyph_height.saturating_sub(first_row);
+        let remaining_bytes = bytes_per_row(glyph_width, 1).saturating_sub(first_byte);
+        if buf.is_empty() && remaining_rows > 0 && remaining_bytes > 0 {
+            return Err(Error::BufferTooSmall);
+        }
+        let tile_row_bytes = remaining_bytes.min(buf.len());
+        // empty rows, or nothing left to read
+        let rows = buf
+            .len()
+            .checked_div(tile_row_bytes)
+            .map_or(0, |fitting_rows| remaining_rows.min(fitting_rows));
+        let length = rows * tile_row_bytes;
+        let mut cursor = SourceReader::new(&self.source);
+        let tile = (first_row, first_byte);
+        let bitmap = (location, stored_row_bytes);
+        self.read_tile(
+            &mut cursor,
+            bitmap,
+            tile,
+            tile_row_bytes,
+            &mut buf[..length],
+        )?;
+        Ok((length, tile_row_bytes))
+    }
+
+    /// Fill `buf` with `tile_row_bytes` bytes of each glyph row, from `(first_row, first_byte)`,
+    /// the bitmap being at `location` with rows of `stored_row_bytes`.
+    ///
+    /// The length of `buf` must be a multiple of `tile_row_bytes` and not go beyond the glyph.
+    fn read_tile(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        (location, stored_row_bytes): (u64, usize),
+        (first_row, first_byte): (usize, usize),
+        tile_row_bytes: usize,
+        buf: &mut [u8],
+    ) -> Result<(), Error> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+        for (row, part) in (first_row..).zip(buf.chunks_exact_mut(tile_row_bytes)) {
+            let row_location = location + (row * stored_row_bytes) as u64;
+            self.read_glyph_row_part(cursor, row_location, first_byte, part)?;
+        }
+        Ok(())
+    }
+
+    /// Gets the absolute offset to the bitmap of the glyph, checking it's inside the bitmap data.
+    fn glyph_bitmap_location(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+        glyph_width: usize,
+        glyph_height: usize,
+    ) -> Result<u64, Error> {
+        let bitmap_offset = self.get_glyph_bitmap_offset(cursor, glyph_index)? as u64;
+        let bitmap_length = (glyph_height * self.original_row_bytes(glyph_width)) as u64;
+        if bitmap_offset + bitmap_length > self.bitmap_data_size {
+            return Err(Error::CorruptedData);
+        }
+        Ok(self.bitmap_data_location + bitmap_offset)
+    }
+
+    /// `(absolute offset, stored row length, metrics)` of the glyph bitmap, for reading it in
+    /// tiles with [PcfFont::read_glyph_tile], or the rows with [PcfFont::read_row_part] somewhere
+    /// else.
+    pub(crate) fn glyph_bitmap_by_index(
+        &self,
+        glyph_index: u16,
+    ) -> Result<(u64, usize, MetricsEntry), Error> {
+        if glyph_index as u32 >= self.glyph_count {
+            return Err(Error::NotFound);
+        }
+        let mut cursor = SourceReader::new(&self.source);
         let metrics = self.get_metrics(&mut cursor, glyph_index)?;
+        let (glyph_width, glyph_height) = metrics.checked_glyph_size()?;
+        let location =
+            self.glyph_bitmap_location(&mut cursor, glyph_index, glyph_width, glyph_height)?;
+        Ok((location, self.original_row_bytes(glyph_width), metrics))
+    }
+
+    /// Read the bytes from `first_byte` of the glyph row at `row_location` into `part`, MSBit
+    /// first and padded to byte.
+    ///
+    /// When the part doesn't end the row, it must end at a multiple of 4 bytes, so the stored
+    /// bytes read don't go beyond it.
+    #[cfg(feature = "embedded-io-async")]
+    pub(crate) fn read_row_part(
+        &self,
+        row_location: u64,
+        first_byte: usize,
+        part: &mut [u8],
+    ) -> Result<(), Error> {
+        let mut cursor = SourceReader::new(&self.source);
+        self.read_glyph_row_part(&mut cursor, row_location, first_byte, part)
+    }
 
-        let glyph_width = metrics.glyph_width() as usize;
-        let glyph_height = metrics.glyph_height() as usize;
-        let original_row_bytes = match self.glyph_row_padding_format {
+    /// Length of a glyph row as stored in the bitmap table.
+    #[inline]
+    fn original_row_bytes(&self, glyph_width: usize) -> usize {
+        match self.glyph_row_padding_format {
             GlyphPaddingFormat::Byte => bytes_per_row(glyph_width, 1),
             GlyphPaddingFormat::Short => bytes_per_row(glyph_width, 2),
             GlyphPaddingFormat::Int => bytes_per_row(glyph_width, 4),
-        };
-        // convert all padding scheme to padding to bytes
+        }
+    }
+
+    /// Bytes in each scan unit are stored in reversed order when the byte order
+    /// and the bit order of the bitmap table disagree.
+    #[inline]
+    fn swap_units(&self) -> bool {
+        self.bitmaps_scan_unit > 1
+            && (self.bitmaps_byte_order == ByteOrder::MsbFirst) != self.bitmaps_msbit_first
+    }
+
+    /// Gets the glyph bitmap of the given code point straight from the font data, without
+    /// copying, along with the glyph's metrics.
+    ///
+    /// The bitmap is the same with [PcfFont::read_glyph_raw]. Only fonts in memory(e.g. `&[u8]`)
+    /// with glyph rows padded to byte and stored MSBit first are supported, others get
+    /// [Error::UnsupportedFormat].
+    pub fn glyph_slice(&self, code_point: u32) -> Result<(&[u8], MetricsEntry), Error> {
+        self.check_borrowable()?;
+        let mut cursor = SourceReader::new(&self.source);
+        let glyph_index = self.get_glyph_index(&mut cursor, code_point)?;
+        self.glyph_slice_at(&mut cursor, glyph_index)
+    }
+
+    /// Same with [PcfFont::glyph_slice], but use the internal glyph index to locate the glyph.
+    pub fn glyph_slice_by_index(&self, glyph_index: u16) -> Result<(&[u8], MetricsEntry), Error> {
+        self.check_borrowable()?;
+        if glyph_index as u32 >= self.glyph_count {
+            return Err(Error::NotFound);
+        }
+        let mut cursor = SourceReader::new(&self.source);
+        self.glyph_slice_at(&mut cursor, glyph_index)
+    }
+
+    /// Whether glyphs can be borrowed from the font data, see [PcfFont::glyph_slice].
+    fn check_borrowable(&self) -> Result<(), Error> {
+        if self.source.as_bytes().is_none()
+            || self.glyph_row_padding_format != GlyphPaddingFormat::Byte
+            || !self.bitmaps_msbit_first
+            || self.swap_units()
+        {
+            return Err(Error::UnsupportedFormat);
+        }
+        Ok(())
+    }
+
+    fn glyph_slice_at(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+    ) -> Result<(&[u8], MetricsEntry), Error> {
+        let data = self.source.as_bytes().ok_or(Error::UnsupportedFormat)?;
+        let metrics = self.get_metrics(cursor, glyph_index)?;
+        let (glyph_width, glyph_height) = metrics.checked_glyph_size()?;
+        let location =
+            self.glyph_bitmap_location(cursor, glyph_index, glyph_width, glyph_height)?;
+        let length = glyph_height * bytes_per_row(glyph_width, 1);
+        let glyph = usize::try_from(location)
+            .ok()
+            .and_then(|start| data.get(start..)?.get(..length))
+            .ok_or(Error::Io)?;
+        Ok((glyph, metrics))
+    }
+
+    /// Read the bytes from `first_byte` of the glyph row at `row_location` into `part`, MSBit
+    /// first and padded to byte.
+    fn read_glyph_row_part(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        row_location: u64,
+        first_byte: usize,
+        part: &mut [u8],
+    ) -> Result<(), Error> {
+        if self.swap_units() {
+            // read the whole units covering the part, the last one may contain padding bytes only
+            let unit = self.bitmaps_scan_unit;
+            let first_unit = first_byte / unit * unit;
+            cursor.seek(row_location + first_unit as u64);
+            let mut unit_buffer: [u8; 4] = [0; 4];
+            for unit_start in (first_unit..first_byte + part.len()).step_by(unit) {
+                cursor.read_exact(&mut unit_buffer[..unit])?;
+                unit_buffer[..unit].reverse();
+                for (index, byte) in (unit_start..).zip(&unit_buffer[..unit]) {
+                    if let Some(target) = index
+                        .checked_sub(first_byte)
+                        .and_then(|index| part.get_mut(index))
+                    {
+                        *target = *byte;
+                    }
+                }
+            }
+        } else {
+            cursor.seek(row_location + first_byte as u64);
+            cursor.read_exact(part)?;
+        }
+        // normalize to MSBit-first, which is what embedded-graphics expects
+        if !self.bitmaps_msbit_first {
+            for byte in part.iter_mut() {
+                *byte = byte.reverse_bits();
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the ink metrics of the glyph, which tightly bound the lit pixels.
+    ///
+    /// The ink metrics table is used if present, otherwise the bounds are computed by scanning
+    /// the glyph bitmap. A glyph without any lit pixel gets a zero sized box at the origin.
+    pub fn get_glyph_ink_metrics(&self, code_point: u32) -> Result<MetricsEntry, Error> {
+        let mut cursor = SourceReader::new(&self.source);
+        let glyph_index = self.get_glyph_index(&mut cursor, code_point)?;
+        self.get_ink_metrics(&mut cursor, glyph_index)
+    }
+
+    /// Same with [PcfFont::get_glyph_ink_metrics], but use the internal glyph index.
+    pub fn get_glyph_ink_metrics_by_index(&self, glyph_index: u16) -> Result<MetricsEntry, Error> {
+        if glyph_index as u32 >= self.glyph_count {
+            return Err(Error::NotFound);
+        }
+        let mut cursor = SourceReader::new(&self.source);
+        self.get_ink_metrics(&mut cursor, glyph_index)
+    }
+
+    fn get_ink_metrics(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+    ) -> Result<MetricsEntry, Error> {
+        if let Some(table) = self.ink_metrics_table {
+            return self.read_metrics(cursor, table, glyph_index);
+        }
+        self.scan_ink_metrics(cursor, glyph_index, &mut [0; 64])
+    }
+
+    /// Compute the ink metrics from the glyph bitmap, scanning the rows a `buf` at a time.
+    fn scan_ink_metrics(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+        buf: &mut [u8],
+    ) -> Result<MetricsEntry, Error> {
+        if buf.is_empty() {
+            return Err(Error::BufferTooSmall);
+        }
+        let metrics = self.get_metrics(cursor, glyph_index)?;
+        let (glyph_width, glyph_height) = metrics.checked_glyph_size()?;
         let standard_row_bytes = bytes_per_row(glyph_width, 1);
-        cursor.seek(io::SeekFrom::Start(
-            (self.bitmap_data_location + bitmap_offset) as u64,
-        ))?;
-        let skip_count = original_row_bytes - standard_row_bytes;
-        // NOTE: this procedure is for MSBit-first glyphs
-        for row in 0..glyph_height {
-            let buf_start = row * standard_row_bytes;
-            let buf_end = buf_start + standard_row_bytes;
-            cursor.read_exact(&mut buf[buf_start..buf_end])?;
-            // skip extra padding bytes
-            cursor.seek_relative(skip_count as i64)?;
+        let location =
+            self.glyph_bitmap_location(cursor, glyph_index, glyph_width, glyph_height)?;
+        let original_row_bytes = self.original_row_bytes(glyph_width) as u64;
+        // (left, right, top, bottom) of the lit pixels, all inclusive
+        let mut ink: Option<(usize, usize, usize, usize)> = None;
+        for y in 0..glyph_height {
+            let row_location = location + y as u64 * original_row_bytes;
+            for first_byte in (0..standard_row_bytes).step_by(buf.len()) {
+                let length = buf.len().min(standard_row_bytes - first_byte);
+                let part = &mut buf[..length];
+                self.read_glyph_row_part(cursor, row_location, first_byte, part)?;
+                let Some(first) = part.iter().position(|byte| *byte != 0) else {
+                    continue;
+                };
+                let last = part.iter().rposition(|byte| *byte != 0).unwrap_or(first);
+                let left = (first_byte + first) * 8 + part[first].leading_zeros() as usize;
+                let right = (first_byte + last) * 8 + 7 - part[last].trailing_zeros() as usize;
+                ink = Some(match ink {
+                    Some((l, r, top, _)) => (l.min(left), r.max(right), top, y),
+                    None => (left, right, y, y),
+                });
+            }
         }
-        // the length of data written, the width of the bitmap
-        let length = glyph_height * standard_row_bytes;
-        Ok((length, metrics))
+
+        let ink_metrics = match ink {
+            Some((left, right, top, bottom)) => MetricsEntry {
+                left_side_bearing: metrics.left_side_bearing + left as i16,
+                right_side_bearing: metrics.left_side_bearing + right as i16 + 1,
+                character_ascent: metrics.character_ascent - top as i16,
+                character_descent: metrics.character_descent - (glyph_height - 1 - bottom) as i16,
+                ..metrics
+            },
+            None => MetricsEntry {
+                left_side_bearing: 0,
+                right_side_bearing: 0,
+                character_ascent: 0,
+                character_descent: 0,
+                ..metrics
+            },
+        };
+        Ok(ink_metrics)
     }
 
     /// Gets only the metrics of the glyph, to calculate width without using the glyph
-    pub fn get_glyph_metrics(&self, code_point: u16) -> Result<MetricsEntry, Error> {
-        let mut cursor = self.data_cursor.clone();
+    ///
+    /// If the metrics of the glyph can't be read, those of the default character are returned.
+    /// A missing glyph is still [Error::NotFound], see [MissingGlyph](crate::MissingGlyph) for
+    /// measuring it.
+    pub fn get_glyph_metrics(&self, code_point: u32) -> Result<MetricsEntry, Error> {
+        let mut cursor = SourceReader::new(&self.source);
         let glyph_index = self.get_glyph_index(&mut cursor, code_point)?;
-        if let Ok(value) = self.get_metrics(&mut cursor, glyph_index) {
-            Ok(value)
-        } else {
-            let glyph_index = self.get_glyph_index(&mut cursor, self.default_char)?;
-            self.get_metrics(&mut cursor, glyph_index)
+        match (
+            self.get_metrics(&mut cursor, glyph_index),
+            self.default_glyph_index(),
+        ) {
+            (Ok(value), _) => Ok(value),
+            (Err(_), Some(glyph_index)) => self.get_metrics(&mut cursor, glyph_index),
+            (Err(error), None) => Err(error),
         }
     }
 
-    fn get_glyph_index(&self, cursor: &mut T, code_point: u16) -> Result<u16, Error> {
+    /// Gets only the metrics of the glyph at the internal glyph index.
+    pub fn get_glyph_metrics_by_index(&self, glyph_index: u16) -> Result<MetricsEntry, Error> {
+        if glyph_index as u32 >= self.glyph_count {
+            return Err(Error::NotFound);
+        }
+        let mut cursor = SourceReader::new(&self.source);
+        self.get_metrics(&mut cursor, glyph_index)
+    }
+
+    /// Gets the scalable width of the glyph, in 1/1000 em.
+    ///
+    /// Returns [Error::NotFound] if the font has no SWIDTHS table.
+    pub fn scalable_width(&self, code_point: u32) -> Result<i32, Error> {
+        let (byte_order, location) = self.swidths_location.ok_or(Error::NotFound)?;
+        let mut cursor = SourceReader::new(&self.source);
+        let glyph_index = self.get_glyph_index(&mut cursor, code_point)?;
+        // NOTE: each scalable width takes 4 bytes(i32)
+        cursor.seek(location + glyph_index as u64 * 4);
+        let mut buffer: [u8; 4] = [0; 4];
+        cursor.read_exact(&mut buffer)?;
+        Ok(byte_order.i32_from_bytes_ref(&buffer))
+    }
+
+    /// Gets the internal glyph index of the code point.
+    pub fn glyph_index(&self, code_point: u32) -> Result<u16, Error> {
+        let mut cursor = SourceReader::new(&self.source);
+        self.get_glyph_index(&mut cursor, code_point)
+    }
+
+    fn get_glyph_index(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        code_point: u32,
+    ) -> Result<u16, Error> {
+        let code_point = self.charset.encode(code_point).ok_or(Error::NotFound)?;
+        self.get_native_glyph_index(cursor, code_point)
+    }
+
+    /// Same with [PcfFont::override_default_char], but checks that the font has a glyph for it.
+    ///
+    /// Returns [Error::NotFound] if it hasn't, the default character is kept then.
+    pub fn try_override_default_char(&mut self, value: u16) -> Result<(), Error> {
+        self.default_char = self.check_default_char(value)?;
+        Ok(())
+    }
+
+    /// The default character if the font has a glyph for it, `None` for `0xFFFF`.
+    fn check_default_char(&self, value: u16) -> Result<Option<u16>, Error> {
+        if value == NO_DEFAULT_CHAR {
+            return Ok(None);
+        }
+        let mut cursor = SourceReader::new(&self.source);
+        self.get_native_glyph_index(&mut cursor, value.into())?;
+        Ok(Some(value))
+    }
+
+    /// Internal glyph index of [PcfFont::default_char], looked up without the charset.
+    pub(crate) fn default_glyph_index(&self) -> Option<u16> {
+        let mut cursor = SourceReader::new(&self.source);
+        self.get_native_glyph_index(&mut cursor, self.default_char?.into())
+            .ok()
+    }
+
+    /// Same with [PcfFont::get_glyph_index], but the code point is in the font's encoding.
+    fn get_native_glyph_index(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        code_point: u32,
+    ) -> Result<u16, Error> {
+        // the encoding table is 2-byte, anything beyond is missing, never truncated
+        let Ok(code_point) = u16::try_from(code_point) else {
+            return Err(Error::NotFound);
+        };
         let enc1 = (code_point >> 8) & 0xFF;
         let enc2 = code_point & 0xFF;
         if !(self.min_byte1..=self.max_byte1).contains(&enc1)
@@ -399,51 +1181,67 @@ where
         }
 
         // for 1 or 2 bytes encoding, the procedure is the same.
-        let indice_offset = (enc1 - self.min_byte1)
-            * (self.max_char_or_byte2 - self.min_char_or_byte2 + 1)
-            + (enc2 - self.min_char_or_byte2);
+        // the ranges are within a byte, checked when loading
+        let indice_offset = (enc1 - self.min_byte1) as u64
+            * (self.max_char_or_byte2 - self.min_char_or_byte2 + 1) as u64
+            + (enc2 - self.min_char_or_byte2) as u64;
         // NOTE: each indice takes 2 bytes(u16)
-        cursor.seek(io::SeekFrom::Start(
-            (self.encoded_glyph_indices_location + (indice_offset as u32) * 2) as u64,
-        ))?;
+        cursor.seek(self.encoded_glyph_indices_location + indice_offset * 2);
         let mut buffer: [u8; 2] = [0; 2];
         cursor.read_exact(&mut buffer[..])?;
-        let glyph_index = u16::from_be_bytes(buffer);
+        let glyph_index = self.encodings_byte_order.u16_from_bytes_ref(&buffer);
         // 0xFFFF means there's no matching glyph
         if glyph_index == 0xFFFF {
             Err(Error::NotFound)
+        } else if glyph_index as u32 >= self.glyph_count {
+            Err(Error::CorruptedData)
         } else {
             Ok(glyph_index)
         }
     }
 
-    fn get_glyph_bitmap_offset(&self, cursor: &mut T, glyph_index: u16) -> Result<u32, Error> {
+    fn get_glyph_bitmap_offset(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+    ) -> Result<u32, Error> {
         let mut buffer: [u8; 4] = [0; 4];
         // NOTE: each glyph location offset takes 4 bytes(u32)
-        cursor.seek(io::SeekFrom::Start(
-            (self.bitmap_position_lut_location + (glyph_index as u32) * 4) as u64,
-        ))?;
+        cursor.seek(self.bitmap_position_lut_location + glyph_index as u64 * 4);
         cursor.read_exact(&mut buffer)?;
-        Ok(u32::from_be_bytes(buffer))
+        Ok(self.bitmaps_byte_order.u32_from_bytes_ref(&buffer))
+    }
+
+    fn get_metrics(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        glyph_index: u16,
+    ) -> Result<MetricsEntry, Error> {
+        self.read_metrics(cursor, self.metrics_table, glyph_index)
     }
 
-    fn get_metrics(&self, cursor: &mut T, glyph_index: u16) -> Result<MetricsEntry, Error> {
-        if self.metrics_compressed {
-            let cursor_offset = self.metrics_data_location + (glyph_index as u32) * 5;
+    fn read_metrics(
+        &self,
+        cursor: &mut SourceReader<'_, T>,
+        table: MetricsTable,
+        glyph_index: u16,
+    ) -> Result<MetricsEntry, Error> {
+        if table.compressed {
+            let cursor_offset = table.data_location + glyph_index as u64 * 5;
             self.get_metrics_compressed(cursor, cursor_offset)
         } else {
-            let cursor_offset = self.metrics_data_location + (glyph_index as u32) * 12;
-            self.get_metrics_standard(cursor, cursor_offset)
+            let cursor_offset = table.data_location + glyph_index as u64 * 12;
+            self.get_metrics_standard(cursor, cursor_offset, table.byte_order)
         }
     }
 
     #[inline]
     fn get_metrics_compressed(
         &self,
-        cursor: &mut T,
-        cursor_offset: u32,
+        cursor: &mut SourceReader<'_, T>,
+        cursor_offset: u64,
     ) -> Result<MetricsEntry, Error> {
-        cursor.seek(io::SeekFrom::Start(cursor_offset as u64))?;
+        cursor.seek(cursor_offset);
         let mut buffer: [u8; 5] = [0; 5];
         cursor.read_exact(&mut buffer)?;
         Ok(MetricsEntry::new_from_compressed(&buffer))
@@ -452,13 +1250,14 @@ where
     #[inline]
     fn get_metrics_standard(
         &self,
-        cursor: &mut T,
-        cursor_offset: u32,
+        cursor: &mut SourceReader<'_, T>,
+        cursor_offset: u64,
+        byte_order: ByteOrder,
     ) -> Result<MetricsEntry, Error> {
-        cursor.seek(io::SeekFrom::Start(cursor_offset as u64))?;
+        cursor.seek(cursor_offset);
         let mut buffer: [u8; 12] = [0; 12];
         cursor.read_exact(&mut buffer)?;
-        Ok(MetricsEntry::new_from_standard(&buffer))
+        Ok(MetricsEntry::new_from_standard(&buffer, byte_order))
     }
 }
 
@@ -469,20 +1268,30 @@ impl<T> Debug for PcfFont<T> {
             .field("ascent", &self.ascent)
             .field("descent", &self.descent)
             .field("bounding_box", &self.bounding_box)
-            .field("metrics_compressed", &self.metrics_compressed)
+            .field("metrics_compressed", &self.metrics_table.compressed)
             .finish_non_exhaustive()
     }
 }
 
-/// Check and load PCF font using given IO buffer.
+/// Check and load PCF font from the given source, e.g. a `&[u8]` or an `io::Cursor`.
 ///
 /// Use this to load the font, never try it manually.
-pub fn load_pcf_font<T>(mut data_cursor: T) -> Result<PcfFont<T>, Error>
+pub fn load_pcf_font<T>(source: T) -> Result<PcfFont<T>, Error>
 where
-    T: io::Read + io::Seek + Clone,
+    T: FontSource,
+{
+    let mut font = load_tables(source)?;
+    font.charset = font.detect_charset();
+    Ok(font)
+}
+
+/// Load the font without looking at the properties, the charset is left unknown.
+pub(crate) fn load_tables<T>(source: T) -> Result<PcfFont<T>, Error>
+where
+    T: FontSource,
 {
     let mut buffer: [u8; 16] = [0; 16];
-    data_cursor.rewind()?;
+    let mut data_cursor = SourceReader::new(&source);
 
     // verify header
     data_cursor.read_exact(&mut buffer[..4])?;
@@ -491,7 +1300,7 @@ where
     }
 
     // read necessary tables(here only the table of content entries)
-    let mut table_toc: [Option<TableTocEntry>; 5] = [None; 5];
+    let mut table_toc: [Option<TableTocEntry>; 9] = [None; 9];
     data_cursor.read_exact(&mut buffer[0..4])?;
     let table_count = u32_from_le_bytes_ref(&buffer[0..4]) as usize;
     for _ in 0..table_count {
@@ -508,6 +1317,10 @@ where
             TableType::BdfEncodings => table_toc[2] = Some(table_toc_entry),
             TableType::BdfAccelerators => table_toc[3] = Some(table_toc_entry),
             TableType::Accelerators => table_toc[4] = Some(table_toc_entry),
+            TableType::Properties => table_toc[5] = Some(table_toc_entry),
+            TableType::GlyphNames => table_toc[6] = Some(table_toc_entry),
+            TableType::InkMetrics => table_toc[7] = Some(table_toc_entry),
+            TableType::Swidths => table_toc[8] = Some(table_toc_entry),
             _ => {}
         }
     }
@@ -521,12 +1334,10 @@ where
         if i.is_none() {
             return Err(Error::CorruptedData);
         }
-        if i.unwrap().format & (PCF_BYTE_MASK | PCF_BIT_MASK) != (PCF_BYTE_MASK | PCF_BIT_MASK) {
-            // NOTE: only support Most Significant Byte first by the moment
-            // NOTE: current implmentation only supports reading Most-Significant-Bit-First glyph data.
-            return Err(Error::UnsupportedFormat);
-        }
     }
+    let bitmaps_msbit_first = table_toc[0].unwrap().format & PCF_BIT_MASK > 0;
+    let bitmaps_byte_order = table_toc[0].unwrap().byte_order();
+    let encodings_byte_order = table_toc[2].unwrap().byte_order();
 
     // Check bitmap format
     /* format contains flags that indicate: */
@@ -537,16 +1348,17 @@ where
     /* what the bits are stored in (bytes, shorts, ints) (format>>4)&3 */
     /*  0=>bytes, 1=>shorts, 2=>ints */
     // So 0xE means: MSByte first, MSBit first, glyph row padded to int(4 bytes)
-    if table_toc[0].unwrap().format & PCF_SCAN_UNIT_MASK != 0 {
-        // only support bits stored in bytes
-        // having no idea of others though
-        return Err(Error::UnsupportedFormat);
-    }
     let glyph_row_padding_format = table_toc[0].unwrap().format & PCF_GLYPH_PAD_MASK;
     // TODO: is this check necessary?
     if glyph_row_padding_format == PCF_GLYPH_PAD_MASK {
         return Err(Error::CorruptedData);
     }
+    let bitmaps_scan_unit = (table_toc[0].unwrap().format & PCF_SCAN_UNIT_MASK) >> 4;
+    // scan units never span across rows
+    if bitmaps_scan_unit > glyph_row_padding_format {
+        return Err(Error::CorruptedData);
+    }
+    let bitmaps_scan_unit = 1 << bitmaps_scan_unit;
     let glyph_row_padding_format =
         GlyphPaddingFormat::from_primitive(glyph_row_padding_format as u8);
 
@@ -554,66 +1366,96 @@ where
 
     // process Bitmaps table
     // not everything is used
-    data_cursor.seek(io::SeekFrom::Start(table_toc[0].unwrap().offset as u64 + 4))?;
+    let bitmaps_toc = table_toc[0].unwrap();
+    data_cursor.seek(bitmaps_toc.locate(4, 4)?);
     data_cursor.read_exact(&mut buffer[0..4])?;
-    let glyph_count = u32_from_be_bytes_ref(&buffer);
-    data_cursor.seek(io::SeekFrom::Current(glyph_count as i64 * 4))?; // seek to bitmapSizes
-    data_cursor.read_exact(&mut buffer[0..12])?;
-    // let bitmap_size = u32_from_be_bytes_ref(&buffer[8..12]); // original i32, should be fine
+    let glyph_count = bitmaps_byte_order.u32_from_bytes_ref(&buffer);
+    let bitmap_position_lut_location = bitmaps_toc.locate(4 + 4, glyph_count as u64 * 4)?;
+    // seek to bitmapSizes, one for each padding format
+    let bitmap_sizes_offset = 4 + 4 + glyph_count as u64 * 4;
+    data_cursor.seek(bitmaps_toc.locate(bitmap_sizes_offset, 16)?);
+    data_cursor.read_exact(&mut buffer[0..16])?;
+    let bitmap_size_start = glyph_row_padding_format as usize * 4;
+    // original i32, should be fine
+    let bitmap_data_size = bitmaps_byte_order
+        .u32_from_bytes_ref(&buffer[bitmap_size_start..bitmap_size_start + 4])
+        as u64;
+    let bitmap_data_location = bitmaps_toc.locate(bitmap_sizes_offset + 16, bitmap_data_size)?;
 
     // process Metrics table
-    // not everything is used
-    data_cursor.seek(io::SeekFrom::Start(table_toc[1].unwrap().offset as u64))?;
-    data_cursor.read_exact(&mut buffer[0..8])?;
-    let metrics_compressed = table_toc[1].unwrap().format & PCF_COMPRESSED_METRICS > 0;
-    let metrics_count = {
-        if metrics_compressed {
-            u16_from_be_bytes_ref(&buffer[4..6]) as u32
-        } else {
-            u32_from_be_bytes_ref(&buffer[4..8])
-        }
-    };
+    let (metrics_table, metrics_count) =
+        MetricsTable::load(&mut data_cursor, table_toc[1].unwrap())?;
     if metrics_count != glyph_count {
         return Err(Error::CorruptedData);
     }
 
+    // process InkMetrics table, optional
+    let ink_metrics_table = match table_toc[7] {
+        Some(toc) => {
+            let (table, count) = MetricsTable::load(&mut data_cursor, toc)?;
+            if count != glyph_count {
+                return Err(Error::CorruptedData);
+            }
+            Some(table)
+        }
+        None => None,
+    };
+
+    // process Swidths table, optional, only the location is kept
+    let swidths_location = match table_toc[8] {
+        Some(toc) => {
+            let byte_order = toc.byte_order();
+            // skip format field
+            data_cursor.seek(toc.locate(4, 4)?);
+            data_cursor.read_exact(&mut buffer[0..4])?;
+            if byte_order.u32_from_bytes_ref(&buffer[0..4]) != glyph_count {
+                return Err(Error::CorruptedData);
+            }
+            Some((byte_order, toc.locate(4 + 4, glyph_count as u64 * 4)?))
+        }
+        None => None,
+    };
+
     // process Encoding table
     // not everything is used
     // skip format field
-    data_cursor.seek(io::SeekFrom::Start(table_toc[2].unwrap().offset as u64 + 4))?;
+    let encodings_toc = table_toc[2].unwrap();
+    data_cursor.seek(encodings_toc.locate(4, 10)?);
     data_cursor.read_exact(&mut buffer[0..10])?;
-    let min_char_or_byte2 = u16_from_be_bytes_ref(&buffer[0..2]);
-    let max_char_or_byte2 = u16_from_be_bytes_ref(&buffer[2..4]);
-    let min_byte1 = u16_from_be_bytes_ref(&buffer[4..6]);
-    let max_byte1 = u16_from_be_bytes_ref(&buffer[6..8]);
-    let default_char = u16_from_be_bytes_ref(&buffer[6..8]);
+    let min_char_or_byte2 = encodings_byte_order.u16_from_bytes_ref(&buffer[0..2]);
+    let max_char_or_byte2 = encodings_byte_order.u16_from_bytes_ref(&buffer[2..4]);
+    let min_byte1 = encodings_byte_order.u16_from_bytes_ref(&buffer[4..6]);
+    let max_byte1 = encodings_byte_order.u16_from_bytes_ref(&buffer[6..8]);
+    let default_char = encodings_byte_order.u16_from_bytes_ref(&buffer[8..10]);
+    // both bytes of the code point are within a byte
+    if min_char_or_byte2 > max_char_or_byte2
+        || max_char_or_byte2 > 0xFF
+        || min_byte1 > max_byte1
+        || max_byte1 > 0xFF
+    {
+        return Err(Error::CorruptedData);
+    }
+    let encoded_glyph_count =
+        (max_char_or_byte2 - min_char_or_byte2 + 1) as u64 * (max_byte1 - min_byte1 + 1) as u64;
+    let encoded_glyph_indices_location =
+        encodings_toc.locate(4 + 5 * 2, encoded_glyph_count * 2)?;
 
     // process Accelerators table
-    // not everything is used
-    // skip format, and some u8 meta data
-    data_cursor.seek(io::SeekFrom::Start(
-        table_toc[3].unwrap().offset as u64 + 4 + 8,
-    ))?;
-    data_cursor.read_exact(&mut buffer[0..8])?;
-    let ascent = i32_from_be_bytes_ref(&buffer[0..4]);
-    let descent = i32_from_be_bytes_ref(&buffer[4..8]);
-    // skip `maxOverlap`
-    data_cursor.seek_relative(4)?;
-    // load ink bounds on demand
+    let accelerators = AcceleratorTable::load(&mut data_cursor, table_toc[3].unwrap())?;
+    let ascent = accelerators.font_ascent;
+    let descent = accelerators.font_descent;
+    // use ink bounds if there are any
     let bounding_box = {
-        if table_toc[3].unwrap().format & PCF_ACCEL_W_INKBOUNDS > 0 {
-            // skip minbounds and maxbounds, use ink_minbounds and ink_maxbounds instead
-            data_cursor.seek_relative(24)?;
-        }
-        data_cursor.read_exact(&mut buffer[0..12])?;
-        let minbounds = MetricsEntry::new_from_standard(&buffer);
-        data_cursor.read_exact(&mut buffer[0..12])?;
-        let maxbounds = MetricsEntry::new_from_standard(&buffer);
-        let width = maxbounds.right_side_bearing - minbounds.left_side_bearing;
-        let height = maxbounds.character_ascent + maxbounds.character_descent;
+        let minbounds = accelerators.ink_minbounds;
+        let maxbounds = accelerators.ink_maxbounds;
+        let bounds = MetricsEntry {
+            left_side_bearing: minbounds.left_side_bearing,
+            ..maxbounds
+        };
+        let (width, height) = bounds.checked_glyph_size()?;
         BoundingBox {
-            width,
-            height,
+            width: width as i16,
+            height: height as i16,
             min_left_bearing: minbounds.left_side_bearing,
             max_right_bearing: maxbounds.right_side_bearing,
             max_ascent: maxbounds.character_ascent,
@@ -621,42 +1463,65 @@ where
         }
     };
 
-    let bitmap_position_lut_location = table_toc[0].unwrap().offset + 4 + 4;
-    let bitmap_data_location = bitmap_position_lut_location + (glyph_count + 4) * 4;
-    let metrics_data_location =
-        table_toc[1].unwrap().offset + 4 + if metrics_compressed { 2 } else { 4 };
-    let encoded_glyph_indices_location = table_toc[2].unwrap().offset + 4 + 5 * 2;
-
-    // println!(
-    //     "Bitmap data location: {}/{}/{}/{}",
-    //     bitmap_data_location, metrics_data_location, encoded_glyph_indices_location, table_toc[2].unwrap().offset
-    // );
+    // process Properties table, only the location is kept
+    // it's just metadata, a broken one is dropped instead of the whole font
+    let properties_table =
+        match table_toc[5].map(|toc| PropertiesTable::load(&mut data_cursor, toc)) {
+            Some(Err(Error::CorruptedData)) | None => None,
+            Some(table) => Some(table?),
+        };
 
-    data_cursor.rewind()?;
+    // process GlyphNames table, only the location is kept, dropped like the properties
+    let glyph_names_table =
+        match table_toc[6].map(|toc| GlyphNamesTable::load(&mut data_cursor, toc)) {
+            Some(Err(Error::CorruptedData)) | None => None,
+            Some(table) => Some(table?),
+        };
 
-    Ok(PcfFont {
-        data_cursor,
+    let mut font = PcfFont {
+        source,
         glyph_count,
         ascent,
         descent,
-        metrics_compressed,
+        metrics_table,
+        ink_metrics_table,
+        swidths_location,
+        bitmaps_byte_order,
+        encodings_byte_order,
+        bitmaps_msbit_first,
+        bitmaps_scan_unit,
         bounding_box,
+        accelerators,
         glyph_row_padding_format,
         min_char_or_byte2,
         max_char_or_byte2,
         min_byte1,
         max_byte1,
-        default_char,
+        default_char: None,
         encoded_glyph_indices_location,
         bitmap_position_lut_location,
         bitmap_data_location,
-        metrics_data_location,
-    })
+        bitmap_data_size,
+        properties_table,
+        glyph_names_table,
+        charset: Charset::Unknown,
+    };
+
+    // keep the default character only if it has a glyph, an unreadable one is just dropped
+    font.default_char = font.check_default_char(default_char).unwrap_or(None);
+
+    Ok(font)
 }
 
 #[cfg(test)]
 mod test {
-    use io::Cursor;
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+
+    use crate::{
+        test_utils::{font_with_broken_properties, FONT_LATIN1_LSBIT_UNIT2, FONT_LATIN1_LSBYTE},
+        OffsetSource,
+    };
 
     use super::*;
 
@@ -666,6 +1531,389 @@ mod test {
     /// Mono font, big endian, glyph row padded to byte
     const FONT_MONO: &[u8] =
         include_bytes!("../test-fonts/fusion-pixel-12px-monospaced-zh_hans.pcf");
+    /// Big endian, glyph row padded to byte, metrics cover the whole character cell
+    const FONT_CELL_METRICS: &[u8] =
+        include_bytes!("../test-fonts/fusion-pixel-12px-proportional-zh_hans.pcf");
+    /// Latin-1 subset of [FONT_VARIABLE], big endian, LSBit first, glyph row padded to int
+    const FONT_LATIN1_LSBIT: &[u8] =
+        include_bytes!("../test-fonts/fusion-pixel-12px-proportional-latin1-lsbit-pad_to_int.pcf");
+
+    /// Latin-1 subset of [FONT_VARIABLE], little endian, glyph row padded to int and stored in ints
+    const FONT_LATIN1_LSBYTE_UNIT4: &[u8] = include_bytes!(
+        "../test-fonts/fusion-pixel-12px-proportional-latin1-lsbyte-unit4-pad_to_int.pcf"
+    );
+
+    /// Asserts that two fonts give the same glyphs for the given code points.
+    fn assert_same_glyphs<A, B>(expected: &PcfFont<A>, actual: &PcfFont<B>, code_points: &str)
+    where
+        A: FontSource,
+        B: FontSource,
+    {
+        let mut expected_buffer: [u8; 50] = [0; 50];
+        let mut actual_buffer: [u8; 50] = [0; 50];
+        for c in code_points.chars() {
+            let (expected_length, expected_metrics) = expected
+                .read_glyph_raw(c as u32, &mut expected_buffer)
+                .unwrap();
+            let (actual_length, actual_metrics) =
+                actual.read_glyph_raw(c as u32, &mut actual_buffer).unwrap();
+            assert_eq!(expected_metrics, actual_metrics, "metrics of {c:?}");
+            assert_eq!(
+                expected_buffer[..expected_length],
+                actual_buffer[..actual_length],
+                "bitmap of {c:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn loading_from_slices() {
+        let font: PcfFont<&'static [u8]> = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let expected = load_pcf_font(FONT_VARIABLE).unwrap();
+        assert_same_glyphs(&expected, &font, " AZaz~¡é÷ÿ");
+        assert_eq!(load_pcf_font(&FONT_VARIABLE[..100]).err(), Some(Error::Io));
+    }
+
+    #[test]
+    fn reading_with_charsets() {
+        let unicode = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        assert_eq!(unicode.charset(), Charset::Unicode);
+        let mut font = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        for charset in [Charset::Iso8859(1), Charset::Unknown] {
+            font.override_charset(charset);
+            assert_same_glyphs(&unicode, &font, "AÄÖÜÿ");
+        }
+        font.override_charset(Charset::Iso8859(1));
+        assert_eq!(font.glyph_index('Ő' as u32), Err(Error::NotFound));
+
+        #[cfg(feature = "charset-iso8859")]
+        {
+            // 0xD5 is 'Õ' in ISO8859-1, but 'Ő' in ISO8859-2
+            font.override_charset(Charset::Iso8859(2));
+            assert_eq!(
+                font.glyph_index('Ő' as u32),
+                unicode.glyph_index('Õ' as u32)
+            );
+            assert_eq!(font.glyph_index('Õ' as u32), Err(Error::NotFound));
+            assert_same_glyphs(&unicode, &font, "Az~");
+        }
+    }
+
+    #[test]
+    fn reading_broken_properties() {
+        let expected = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        for broken_table in [true, false] {
+            let data = font_with_broken_properties(broken_table);
+            // only the metadata is lost, not the glyphs
+            let font = load_pcf_font(&data[..]).unwrap();
+            assert_eq!(font.charset(), Charset::Unknown);
+            assert_same_glyphs(&expected, &font, "AÄÖÜÿ");
+            if broken_table {
+                assert_eq!(font.properties().count(), 0);
+            } else {
+                assert_eq!(font.property("FONT"), Err(Error::CorruptedData));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "charset-gb2312"))]
+    fn reading_without_charset_table() {
+        let mut data = [0; FONT_LATIN1_LSBYTE.len()];
+        data.copy_from_slice(FONT_LATIN1_LSBYTE);
+        let start = data.windows(9).position(|w| w == b"ISO10646\0").unwrap();
+        data[start..start + 9].copy_from_slice(b"GB2312.1\0");
+        let mut font = load_pcf_font(&data[..]).unwrap();
+        assert_eq!(font.charset(), Charset::Gb2312);
+        // Unicode can't be translated, so nothing is found rather than the wrong glyphs
+        for c in "AÄÿ".chars() {
+            assert_eq!(font.glyph_index(c as u32), Err(Error::NotFound));
+        }
+        // the native code points are still usable on request
+        font.override_charset(Charset::Unknown);
+        let unicode = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        assert_same_glyphs(&unicode, &font, "AÄÖÜÿ");
+    }
+
+    #[test]
+    fn reading_charset_from_xlfd() {
+        let mut data = [0; FONT_LATIN1_LSBYTE.len()];
+        data.copy_from_slice(FONT_LATIN1_LSBYTE);
+        fn patch(data: &mut [u8], from: &[u8], to: &[u8]) {
+            let start = data.windows(from.len()).position(|w| w == from).unwrap();
+            data[start..start + to.len()].copy_from_slice(to);
+        }
+        // hide CHARSET_REGISTRY, the registry of the XLFD name is used instead
+        patch(&mut data, b"CHARSET_REGISTRY\0", b"CHARSET_REGISTRX\0");
+        patch(&mut data, b"-ISO10646-1\0", b"-ISO8859-15\0");
+        // the CHARSET_ENCODING property("1") still wins over the field of the name
+        let font = load_pcf_font(&data[..]).unwrap();
+        assert_eq!(font.charset(), Charset::Iso8859(1));
+        assert_eq!(font.font_info().unwrap().charset(), font.charset());
+        // with both properties hidden, the name decides alone
+        patch(&mut data, b"CHARSET_ENCODING\0", b"CHARSET_ENCODINX\0");
+        let font = load_pcf_font(&data[..]).unwrap();
+        assert_eq!(font.charset(), Charset::Iso8859(15));
+        assert_eq!(font.font_info().unwrap().charset(), font.charset());
+    }
+
+    #[test]
+    fn falling_back_to_default_char_metrics() {
+        /// Source failing to read anything within `broken`.
+        struct BrokenSource {
+            data: &'static [u8],
+            broken: core::ops::Range<u64>,
+        }
+
+        impl FontSource for BrokenSource {
+            fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+                if offset < self.broken.end && self.broken.start < offset + buf.len() as u64 {
+                    return Err(Error::Io);
+                }
+                self.data.read_at(offset, buf)
+            }
+        }
+
+        let expected = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let (offset, length) = expected.index_tables()[2];
+        let entry_size = (length / expected.glyph_count() as usize) as u64;
+        let start = offset + expected.glyph_index('A' as u32).unwrap() as u64 * entry_size;
+        let mut font = load_pcf_font(BrokenSource {
+            data: FONT_LATIN1_LSBYTE,
+            broken: start..start + entry_size,
+        })
+        .unwrap();
+        assert_eq!(font.get_glyph_metrics('A' as u32), Err(Error::Io));
+        font.override_default_char('?' as u16);
+        assert_eq!(
+            font.get_glyph_metrics('A' as u32),
+            expected.get_glyph_metrics('?' as u32)
+        );
+        // a missing glyph isn't replaced
+        assert_eq!(font.get_glyph_metrics(0x4E00), Err(Error::NotFound));
+    }
+
+    #[test]
+    fn reading_default_char() {
+        let font = load_pcf_font(FONT_CELL_METRICS).unwrap();
+        assert_eq!(font.default_char(), Some(0xFFFE));
+        assert_eq!(
+            font.glyph_index(0xFFFE),
+            font.glyph_index_by_name(".notdef")
+        );
+
+        // 0xFFFF means no default character
+        let mut font = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        assert_eq!(font.default_char(), None);
+        font.try_override_default_char('?' as u16).unwrap();
+        assert_eq!(font.default_char(), Some(0x3F));
+        assert_eq!(
+            font.default_glyph_index(),
+            font.glyph_index('?' as u32).ok()
+        );
+        // no glyph, the default character is kept
+        assert_eq!(font.try_override_default_char(0x0141), Err(Error::NotFound));
+        assert_eq!(font.default_char(), Some(0x3F));
+        font.try_override_default_char(0xFFFF).unwrap();
+        assert_eq!(font.default_char(), None);
+        // unchecked, but there's no glyph to draw
+        font.override_default_char(0x0141);
+        assert_eq!(font.default_char(), Some(0x0141));
+        assert_eq!(font.default_glyph_index(), None);
+        font.override_default_char(0xFFFF);
+        assert_eq!(font.default_char(), None);
+
+        // the encoding header: 0x20..=0xFF, a single byte, no default character
+        let mut data = [0; FONT_LATIN1_LSBYTE.len()];
+        data.copy_from_slice(FONT_LATIN1_LSBYTE);
+        let header: &[u8] = &[0x20, 0, 0xFF, 0, 0, 0, 0, 0, 0xFF, 0xFF];
+        let start = data.windows(10).position(|w| w == header).unwrap() + 8;
+        for (default_char, expected) in [
+            (0x0041, Some(0x0041)),
+            // out of the encoding ranges
+            (0x0010, None),
+            (0x0141, None),
+        ] {
+            data[start..start + 2].copy_from_slice(&u16::to_le_bytes(default_char));
+            let font = load_pcf_font(&data[..]).unwrap();
+            assert_eq!(font.default_char(), expected, "{default_char:#06X}");
+        }
+    }
+
+    #[test]
+    fn reading_code_points_beyond_bmp() {
+        let font = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let mut buffer: [u8; 50] = [0; 50];
+        assert!(font.glyph_index('A' as u32).is_ok());
+        // never truncated to 'A'
+        for code_point in [0x1_0041, 0x10_0041, u32::MAX] {
+            assert_eq!(font.glyph_index(code_point), Err(Error::NotFound));
+            assert_eq!(font.get_glyph_metrics(code_point), Err(Error::NotFound));
+            assert_eq!(
+                font.read_glyph_raw(code_point, &mut buffer),
+                Err(Error::NotFound)
+            );
+            assert_eq!(font.glyph_slice(code_point), Err(Error::NotFound));
+        }
+    }
+
+    #[test]
+    fn reading_glyph_slices() {
+        let mut buffer: [u8; 50] = [0; 50];
+        for data in [FONT_CELL_METRICS, FONT_MONO, FONT_LATIN1_LSBYTE] {
+            let font = load_pcf_font(data).unwrap();
+            for c in "AZaz~é".chars() {
+                let (length, metrics) = font.read_glyph_raw(c as u32, &mut buffer).unwrap();
+                assert_eq!(
+                    font.glyph_slice(c as u32),
+                    Ok((&buffer[..length], metrics)),
+                    "glyph of {c:?}"
+                );
+            }
+            assert_eq!(font.glyph_slice(0xFFFF), Err(Error::NotFound));
+        }
+
+        let mut storage = [0xFF; 10000];
+        let length = FONT_LATIN1_LSBYTE.len();
+        storage[100..100 + length].copy_from_slice(FONT_LATIN1_LSBYTE);
+        let font = load_pcf_font(OffsetSource::new(&storage[..], 100, length as u64)).unwrap();
+        let expected = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        assert_eq!(
+            font.glyph_slice('A' as u32),
+            expected.glyph_slice('A' as u32)
+        );
+        for data in [FONT_VARIABLE, FONT_LATIN1_LSBIT, FONT_LATIN1_LSBIT_UNIT2] {
+            let font = load_pcf_font(data).unwrap();
+            assert_eq!(font.glyph_slice('A' as u32), Err(Error::UnsupportedFormat));
+        }
+    }
+
+    #[test]
+    fn reading_glyph_rows() {
+        let mut expected_buffer: [u8; 50] = [0; 50];
+        let mut glyph: [u8; 50] = [0; 50];
+        let mut buffer: [u8; 5] = [0; 5];
+        for data in [FONT_VARIABLE, FONT_LATIN1_LSBIT_UNIT2] {
+            let font = load_pcf_font(data).unwrap();
+            for c in "Aé~世".chars() {
+                let Ok(expected) = font.read_glyph_raw(c as u32, &mut expected_buffer) else {
+                    continue;
+                };
+                // read in bands of rows fitting in the small buffer
+                let mut length = 0;
+                let mut row = 0;
+                loop {
+                    let (band_length, metrics) =
+                        font.read_glyph_rows(c as u32, row, &mut buffer).unwrap();
+                    assert_eq!(metrics, expected.1);
+                    if band_length == 0 {
+                        break;
+                    }
+                    glyph[length..length + band_length].copy_from_slice(&buffer[..band_length]);
+                    length += band_length;
+                    row += band_length / (metrics.glyph_width() as usize).div_ceil(8);
+                }
+                assert_eq!(length, expected.0, "length of {c:?}");
+                assert_eq!(
+                    glyph[..length],
+                    expected_buffer[..length],
+                    "bitmap of {c:?}"
+                );
+            }
+        }
+
+        let font = load_pcf_font(FONT_VARIABLE).unwrap();
+        assert_eq!(
+            font.read_glyph_rows('世' as u32, 0, &mut [0]),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(
+            font.read_glyph_rows(0xFFFF, 0, &mut buffer),
+            Err(Error::NotFound)
+        );
+    }
+
+    #[test]
+    fn reading_glyph_tiles() {
+        let mut expected_buffer: [u8; 50] = [0; 50];
+        for data in [
+            FONT_VARIABLE,
+            FONT_LATIN1_LSBYTE_UNIT4,
+            FONT_LATIN1_LSBIT_UNIT2,
+        ] {
+            let font = load_pcf_font(data).unwrap();
+            for c in "Aé~世".chars() {
+                let Ok(glyph_index) = font.glyph_index(c as u32) else {
+                    continue;
+                };
+                let (length, metrics) =
+                    font.read_glyph_raw(c as u32, &mut expected_buffer).unwrap();
+                let row_bytes = (metrics.glyph_width() as usize).div_ceil(8);
+                let bitmap = font.glyph_bitmap_by_index(glyph_index).unwrap();
+                assert_eq!(bitmap.2, metrics);
+                // tiles narrower than the rows, and of several rows
+                for buffer in [&mut [0; 1][..], &mut [0; 3]] {
+                    let mut glyph: [u8; 50] = [0; 50];
+                    for first_byte in 0..row_bytes {
+                        let mut row = 0;
+                        loop {
+                            let (tile_length, tile_row_bytes) = font
+                                .read_glyph_tile(bitmap, row, first_byte, buffer)
+                                .unwrap();
+                            if tile_length == 0 {
+                                break;
+                            }
+                            let tile = buffer[..tile_length].chunks(tile_row_bytes);
+                            for (y, part) in (row..).zip(tile) {
+                                let start = y * row_bytes + first_byte;
+                                glyph[start..start + part.len()].copy_from_slice(part);
+                            }
+                            row += tile_length / tile_row_bytes;
+                        }
+                    }
+                    assert_eq!(
+                        glyph[..length],
+                        expected_buffer[..length],
+                        "bitmap of {c:?}"
+                    );
+                }
+            }
+        }
+
+        let font = load_pcf_font(FONT_VARIABLE).unwrap();
+        let glyph_index = font.glyph_index('世' as u32).unwrap();
+        let bitmap = font.glyph_bitmap_by_index(glyph_index).unwrap();
+        assert_eq!(
+            font.read_glyph_tile(bitmap, 0, 0, &mut []),
+            Err(Error::BufferTooSmall)
+        );
+        let (length, _) = font.read_glyph_tile(bitmap, 0, 2, &mut [0; 5]).unwrap();
+        assert_eq!(length, 0);
+        assert_eq!(
+            font.glyph_bitmap_by_index(font.glyph_count() as u16),
+            Err(Error::NotFound)
+        );
+    }
+
+    #[test]
+    fn scanning_ink_metrics_in_chunks() {
+        // the ink metrics table of the Latin-1 font is what the bitmaps give
+        let expected = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        for data in [FONT_LATIN1_LSBYTE, FONT_LATIN1_LSBIT_UNIT2] {
+            let font = load_pcf_font(data).unwrap();
+            let mut cursor = SourceReader::new(&font.source);
+            for c in "Hello, World! ÄÖÜ¿".chars() {
+                let glyph_index = font.glyph_index(c as u32).unwrap();
+                for buffer in [&mut [0; 1][..], &mut [0; 64]] {
+                    assert_eq!(
+                        font.scan_ink_metrics(&mut cursor, glyph_index, buffer),
+                        expected.get_glyph_ink_metrics(c as u32),
+                        "ink metrics of {c:?}"
+                    );
+                }
+            }
+        }
+    }
 
     #[test]
     #[cfg(feature = "std")]
@@ -677,6 +1925,134 @@ mod test {
         let _ = load_pcf_font(cursor).unwrap();
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_loading_lsbyte_first_font() {
+        let expected = load_pcf_font(Cursor::new(FONT_VARIABLE)).unwrap();
+        let font = load_pcf_font(Cursor::new(FONT_LATIN1_LSBYTE)).unwrap();
+        assert_eq!(font.ascent(), expected.ascent());
+        assert_eq!(font.bounding_box(), expected.bounding_box());
+        assert_same_glyphs(&expected, &font, "Hello, World! ÄÖÜ¿");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_loading_lsbit_first_font() {
+        let expected = load_pcf_font(Cursor::new(FONT_VARIABLE)).unwrap();
+        let font = load_pcf_font(Cursor::new(FONT_LATIN1_LSBIT)).unwrap();
+        assert_same_glyphs(&expected, &font, "Hello, World! ÄÖÜ¿");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_loading_multi_byte_scan_unit_fonts() {
+        let expected = load_pcf_font(Cursor::new(FONT_VARIABLE)).unwrap();
+        let font = load_pcf_font(Cursor::new(FONT_LATIN1_LSBYTE_UNIT4)).unwrap();
+        assert_same_glyphs(&expected, &font, "Hello, World! ÄÖÜ¿");
+        let font = load_pcf_font(Cursor::new(FONT_LATIN1_LSBIT_UNIT2)).unwrap();
+        assert_same_glyphs(&expected, &font, "Hello, World! ÄÖÜ¿");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_reading_ink_metrics() {
+        // computed from the bitmaps
+        let computed = load_pcf_font(Cursor::new(FONT_CELL_METRICS)).unwrap();
+        // read from the ink metrics table
+        let font = load_pcf_font(Cursor::new(FONT_LATIN1_LSBYTE)).unwrap();
+        for c in "Hello, World! ÄÖÜ¿".chars() {
+            assert_eq!(
+                computed.get_glyph_ink_metrics(c as u32),
+                font.get_glyph_ink_metrics(c as u32),
+                "ink metrics of {c:?}"
+            );
+        }
+        let metrics = computed.get_glyph_metrics('A' as u32).unwrap();
+        let ink_metrics = computed.get_glyph_ink_metrics('A' as u32).unwrap();
+        assert_eq!(metrics.glyph_height(), 18);
+        assert_eq!(ink_metrics.glyph_height(), 9);
+        assert_eq!(ink_metrics.character_width, metrics.character_width);
+        let blank = computed.get_glyph_ink_metrics(' ' as u32).unwrap();
+        assert_eq!((blank.glyph_width(), blank.glyph_height()), (0, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_reading_scalable_widths() {
+        let font = load_pcf_font(Cursor::new(FONT_VARIABLE)).unwrap();
+        assert_eq!(font.scalable_width('A' as u32), Ok(640));
+        assert_eq!(font.scalable_width('世' as u32), Ok(960));
+        let font = load_pcf_font(Cursor::new(FONT_LATIN1_LSBYTE)).unwrap();
+        assert_eq!(font.scalable_width('A' as u32), Ok(640));
+        assert_eq!(font.scalable_width('世' as u32), Err(Error::NotFound));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_reading_accelerators() {
+        let font = load_pcf_font(Cursor::new(FONT_MONO)).unwrap();
+        let accelerators = font.accelerators();
+        assert!(accelerators.no_overlap);
+        assert!(!accelerators.terminal_font);
+        assert_eq!(accelerators.draw_direction, DrawDirection::LeftToRight);
+        assert_eq!(accelerators.font_ascent, font.ascent());
+        assert_eq!(accelerators.ink_minbounds, accelerators.minbounds);
+        assert_eq!(
+            accelerators.maxbounds.character_ascent() as i32 - accelerators.font_ascent,
+            font.bounding_box().max_ascent as i32 - font.ascent()
+        );
+
+        let font = load_pcf_font(Cursor::new(FONT_LATIN1_LSBYTE)).unwrap();
+        let expected = load_pcf_font(Cursor::new(FONT_VARIABLE)).unwrap();
+        assert_eq!(font.accelerators(), expected.accelerators());
+    }
+
+    /// Use every part of the font, errors are fine but nothing should panic.
+    #[cfg(feature = "std")]
+    fn use_font(data: &[u8]) {
+        let Ok(font) = load_pcf_font(Cursor::new(data)) else {
+            return;
+        };
+        let _ = font.properties().count();
+        let _ = font.font_info();
+        let _ = font.glyph_index_by_name(".notdef");
+        let mut buffer: [u8; 256] = [0; 256];
+        for glyph_index in 0..4 {
+            let _ = font.glyph_name(glyph_index, &mut buffer);
+            let _ = font.read_glyph_raw_by_index(glyph_index, &mut buffer);
+        }
+        for code_point in [
+            0x0020,
+            0x0041,
+            0x00E9,
+            0x00FF,
+            0x4E16,
+            font.default_char().map_or(0, u32::from),
+        ] {
+            let _ = font.read_glyph_raw(code_point, &mut buffer);
+            let _ = font.get_glyph_metrics(code_point);
+            let _ = font.get_glyph_ink_metrics(code_point);
+            let _ = font.scalable_width(code_point);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_loading_malformed_fonts() {
+        let mut data = FONT_LATIN1_LSBYTE.to_vec();
+        for length in 0..data.len() {
+            use_font(&data[..length]);
+        }
+        for i in 0..data.len() {
+            let original = data[i];
+            for value in [0x00, 0x7F, 0x80, 0xFF] {
+                data[i] = value;
+                use_font(&data);
+            }
+            data[i] = original;
+        }
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn std_loading_glyphs() {
@@ -684,7 +2060,7 @@ mod test {
         let cursor = Cursor::new(FONT_VARIABLE);
         let font = load_pcf_font(cursor).unwrap();
         println!("font: {:?}", font);
-        let (length, metrics) = font.read_glyph_raw('嗨' as u16, &mut buffer).unwrap();
+        let (length, metrics) = font.read_glyph_raw('嗨' as u32, &mut buffer).unwrap();
         let width = metrics.glyph_width() as usize;
         println!("data length: {length}, glyph width: {width}");
         if width == 0 {
@@ -699,7 +2075,7 @@ mod test {
             for pixels in buffer[row_start..row_end].iter() {
                 print!("{:>08b}", pixels)
             }
-            println!("");
+            println!();
         }
     }
 }
@@ -0,0 +1,156 @@
+//! Preloaded glyph index, so looking up glyphs doesn't touch the storage.
+
+use crate::{Error, FontSource, PcfFont};
+
+/// A font source keeping the tables used to look up glyphs in RAM, created by
+/// [PcfFont::preload_index].
+///
+/// Only the glyph bitmaps and the rarely used tables are read from the underlying source.
+#[derive(Debug)]
+pub struct PreloadedSource<S, B> {
+    source: S,
+    buffer: B,
+    /// `(absolute offset, length)` of each table, stored one after another in `buffer`
+    tables: [(u64, usize); 4],
+}
+
+impl<S, B> PreloadedSource<S, B> {
+    /// Gets the underlying source and the buffer back.
+    pub fn into_inner(self) -> (S, B) {
+        (self.source, self.buffer)
+    }
+}
+
+impl<S, B> FontSource for PreloadedSource<S, B>
+where
+    S: FontSource,
+    B: AsRef<[u8]>,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let mut start = 0;
+        for (location, length) in self.tables {
+            let end = offset.checked_add(buf.len() as u64);
+            if offset >= location && end.is_some_and(|end| end <= location + length as u64) {
+                let start = start + (offset - location) as usize;
+                buf.copy_from_slice(&self.buffer.as_ref()[start..start + buf.len()]);
+                return Ok(());
+            }
+            start += length;
+        }
+        self.source.read_at(offset, buf)
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> Option<&[u8]> {
+        self.source.as_bytes()
+    }
+}
+
+impl<T> PcfFont<T>
+where
+    T: FontSource,
+{
+    /// Size of the buffer needed by [PcfFont::preload_index].
+    pub fn index_size(&self) -> usize {
+        self.index_tables().iter().map(|(_, length)| length).sum()
+    }
+
+    /// Load the encoding indices, the bitmap offsets and the metrics into `buffer`, so looking up
+    /// glyphs, [PcfFont::get_glyph_metrics] and measuring text never touch the storage.
+    ///
+    /// The buffer can be a caller-provided `&mut [u8]` or, with `alloc`, a
+    /// `vec![0; font.index_size()]`. Returns [Error::BufferTooSmall] if it's smaller than
+    /// [PcfFont::index_size].
+    pub fn preload_index<B>(self, mut buffer: B) -> Result<PcfFont<PreloadedSource<T, B>>, Error>
+    where
+        B: AsRef<[u8]> + AsMut<[u8]>,
+    {
+        if buffer.as_mut().len() < self.index_size() {
+            return Err(Error::BufferTooSmall);
+        }
+        let tables = self.index_tables();
+        let mut start = 0;
+        for (location, length) in tables {
+            self.source
+                .read_at(location, &mut buffer.as_mut()[start..start + length])?;
+            start += length;
+        }
+        let font = self.with_source(());
+        Ok(font.with_source(PreloadedSource {
+            source: self.source,
+            buffer,
+            tables,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        pixelcolor::BinaryColor,
+        prelude::Point,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    use crate::{
+        load_pcf_font,
+        test_utils::{CountingSource, FONT_LATIN1_LSBYTE},
+        MeasureMode, PcfFontStyleBuilder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn preloading_index() {
+        let source = CountingSource::new(FONT_LATIN1_LSBYTE);
+        let expected = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let font = load_pcf_font(&source).unwrap();
+        let mut buffer = [0; 4000];
+        assert!(font.index_size() < buffer.len());
+        let small_font = load_pcf_font(&source).unwrap();
+        let index_size = small_font.index_size();
+        assert_eq!(
+            small_font
+                .preload_index(&mut buffer[..index_size - 1])
+                .err(),
+            Some(Error::BufferTooSmall)
+        );
+        let font = font.preload_index(&mut buffer[..]).unwrap();
+
+        source.reads.set(0);
+        for c in "Aé~".chars() {
+            assert_eq!(
+                font.get_glyph_metrics(c as u32),
+                expected.get_glyph_metrics(c as u32)
+            );
+            assert_eq!(
+                font.get_glyph_ink_metrics(c as u32),
+                expected.get_glyph_ink_metrics(c as u32)
+            );
+        }
+        assert_eq!(font.glyph_index(0x4E16), Err(Error::NotFound));
+        for measure_mode in [MeasureMode::FontBounds, MeasureMode::InkBounds] {
+            let style = PcfFontStyleBuilder::<_, BinaryColor>::new(&font)
+                .measure_mode(measure_mode)
+                .build();
+            let expected_style = PcfFontStyleBuilder::<_, BinaryColor>::new(&expected)
+                .measure_mode(measure_mode)
+                .build();
+            assert_eq!(
+                style.measure_string("Aé~", Point::zero(), Baseline::Top),
+                expected_style.measure_string("Aé~", Point::zero(), Baseline::Top)
+            );
+        }
+        assert_eq!(source.reads.get(), 0);
+
+        // only the bitmap is read
+        let mut buffer: [u8; 32] = [0; 32];
+        let mut expected_buffer: [u8; 32] = [0; 32];
+        assert_eq!(
+            font.read_glyph_raw('A' as u32, &mut buffer),
+            expected.read_glyph_raw('A' as u32, &mut expected_buffer)
+        );
+        assert_eq!(buffer, expected_buffer);
+        assert!(source.reads.get() > 0);
+    }
+}
@@ -0,0 +1,313 @@
+//! PCF properties table.
+//!
+//! The properties table holds the font metadata as name/value pairs, the names and the string
+//! values are stored in a string pool following the property entries.
+//!
+//! Properties are read on demand with [PcfFont::properties] and [PcfFont::property], nothing is
+//! kept in memory except the table location.
+
+use core::fmt::Debug;
+
+use crate::{
+    parser::TableTocEntry,
+    source::{FontSource, SourceReader},
+    utils::ByteOrder,
+    Error, PcfFont,
+};
+
+/// Maximum length of a string kept by [PropertyString], same with the XLFD font name limit.
+pub const MAX_PROPERTY_STRING_LENGTH: usize = 255;
+
+/// Each property entry takes 9 bytes: name offset(i32), is string(i8), value(i32).
+const PROPERTY_ENTRY_SIZE: u64 = 9;
+
+/// Location of the properties table contents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PropertiesTable {
+    byte_order: ByteOrder,
+    count: u32,
+    /// The absolute offset to the first property entry
+    entries_location: u64,
+    /// The absolute offset to the string pool
+    strings_location: u64,
+    strings_size: u32,
+}
+
+impl PropertiesTable {
+    /// Load the properties table header described by `toc`.
+    pub(crate) fn load<T>(
+        cursor: &mut SourceReader<'_, T>,
+        toc: TableTocEntry,
+    ) -> Result<Self, Error>
+    where
+        T: FontSource + ?Sized,
+    {
+        let byte_order = toc.byte_order();
+        let mut buffer: [u8; 4] = [0; 4];
+        // skip format field
+        cursor.seek(toc.locate(4, 4)?);
+        cursor.read_exact(&mut buffer)?;
+        let count = byte_order.u32_from_bytes_ref(&buffer);
+        // entries are padded to 4 bytes
+        let entries_size = (count as u64 * PROPERTY_ENTRY_SIZE).next_multiple_of(4);
+        let entries_location = toc.locate(4 + 4, entries_size)?;
+        cursor.seek(toc.locate(4 + 4 + entries_size, 4)?);
+        cursor.read_exact(&mut buffer)?;
+        let strings_size = byte_order.u32_from_bytes_ref(&buffer);
+        let strings_location = toc.locate(4 + 4 + entries_size + 4, strings_size as u64)?;
+        Ok(Self {
+            byte_order,
+            count,
+            entries_location,
+            strings_location,
+            strings_size,
+        })
+    }
+}
+
+/// A string atom from the properties table.
+///
+/// PCF doesn't specify the encoding of strings, most fonts use ASCII or UTF-8.
+/// Strings longer than [MAX_PROPERTY_STRING_LENGTH] are truncated.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PropertyString {
+    data: [u8; MAX_PROPERTY_STRING_LENGTH],
+    length: u8,
+}
+
+impl PropertyString {
+    /// Create a string from raw bytes, truncated to [MAX_PROPERTY_STRING_LENGTH].
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Self {
+        let length = bytes.len().min(MAX_PROPERTY_STRING_LENGTH);
+        let mut data = [0; MAX_PROPERTY_STRING_LENGTH];
+        data[..length].copy_from_slice(&bytes[..length]);
+        Self {
+            data,
+            length: length as u8,
+        }
+    }
+
+    /// Raw bytes of the string, without the trailing NUL.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.length as usize]
+    }
+
+    /// The string as `&str`, `None` if it's not valid UTF-8.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(self.as_bytes()).ok()
+    }
+}
+
+impl Debug for PropertyString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.as_str() {
+            Some(value) => Debug::fmt(value, f),
+            None => Debug::fmt(self.as_bytes(), f),
+        }
+    }
+}
+
+/// Value of a property.
+// boxing is not an option without alloc
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyValue {
+    String(PropertyString),
+    Integer(i32),
+}
+
+impl PropertyValue {
+    /// The string value, `None` if it's an integer property.
+    #[inline]
+    pub fn as_string(&self) -> Option<&PropertyString> {
+        match self {
+            Self::String(value) => Some(value),
+            Self::Integer(_) => None,
+        }
+    }
+
+    /// The integer value, `None` if it's a string property.
+    #[inline]
+    pub fn as_integer(&self) -> Option<i32> {
+        match self {
+            Self::String(_) => None,
+            Self::Integer(value) => Some(*value),
+        }
+    }
+}
+
+/// A name/value pair from the properties table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Property {
+    pub name: PropertyString,
+    pub value: PropertyValue,
+}
+
+/// Iterator over the properties of a font, created by [PcfFont::properties].
+pub struct Properties<'a, T: ?Sized> {
+    cursor: SourceReader<'a, T>,
+    table: Option<PropertiesTable>,
+    index: u32,
+}
+
+impl<T> Properties<'_, T>
+where
+    T: FontSource + ?Sized,
+{
+    fn read_property(&mut self, table: PropertiesTable) -> Result<Property, Error> {
+        let mut buffer: [u8; PROPERTY_ENTRY_SIZE as usize] = [0; PROPERTY_ENTRY_SIZE as usize];
+        self.cursor
+            .seek(table.entries_location + self.index as u64 * PROPERTY_ENTRY_SIZE);
+        self.cursor.read_exact(&mut buffer)?;
+        let name_offset = table.byte_order.u32_from_bytes_ref(&buffer[0..4]);
+        let is_string = buffer[4] != 0;
+        let value = table.byte_order.i32_from_bytes_ref(&buffer[5..9]);
+
+        let name = self.read_string(table, name_offset)?;
+        let value = if is_string {
+            PropertyValue::String(self.read_string(table, value as u32)?)
+        } else {
+            PropertyValue::Integer(value)
+        };
+        Ok(Property { name, value })
+    }
+
+    fn read_string(
+        &mut self,
+        table: PropertiesTable,
+        offset: u32,
+    ) -> Result<PropertyString, Error> {
+        let mut string = PropertyString {
+            data: [0; MAX_PROPERTY_STRING_LENGTH],
+            length: 0,
+        };
+        if offset >= table.strings_size {
+            return Err(Error::CorruptedData);
+        }
+        self.cursor.seek(table.strings_location + offset as u64);
+        // never read beyond the string pool
+        let available = (table.strings_size - offset).min(MAX_PROPERTY_STRING_LENGTH as u32);
+        let available = available as usize;
+        // read in chunks until the NUL terminator shows up
+        let mut length = 0;
+        while length < MAX_PROPERTY_STRING_LENGTH {
+            if length == available {
+                // the string pool ends without a terminator
+                return Err(Error::CorruptedData);
+            }
+            // read in chunks, most strings are short
+            let end = available.min(length + 32);
+            let count = end - length;
+            self.cursor.read_exact(&mut string.data[length..end])?;
+            if let Some(end) = string.data[length..length + count]
+                .iter()
+                .position(|byte| *byte == 0)
+            {
+                length += end;
+                break;
+            }
+            length += count;
+        }
+        string.data[length..].fill(0);
+        string.length = length as u8;
+        Ok(string)
+    }
+}
+
+impl<T> Iterator for Properties<'_, T>
+where
+    T: FontSource + ?Sized,
+{
+    type Item = Result<Property, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table?;
+        if self.index >= table.count {
+            return None;
+        }
+        let property = self.read_property(table);
+        self.index += 1;
+        Some(property)
+    }
+}
+
+impl<T> PcfFont<T> {
+    /// Number of properties, 0 if the font has no properties table.
+    #[cfg(feature = "embedded-io-async")]
+    pub(crate) fn property_count(&self) -> u32 {
+        self.properties_table.map_or(0, |table| table.count)
+    }
+}
+
+impl<T> PcfFont<T>
+where
+    T: FontSource,
+{
+    /// Iterate over all the properties of the font.
+    ///
+    /// The iterator is empty if the font has no properties table.
+    pub fn properties(&self) -> Properties<'_, T> {
+        Properties {
+            cursor: SourceReader::new(&self.source),
+            table: self.properties_table,
+            index: 0,
+        }
+    }
+
+    /// Read the property at `index`, without going through the ones before.
+    #[cfg(feature = "embedded-io-async")]
+    pub(crate) fn property_at(&self, index: u32) -> Option<Result<Property, Error>> {
+        Properties {
+            cursor: SourceReader::new(&self.source),
+            table: self.properties_table,
+            index,
+        }
+        .next()
+    }
+
+    /// Look up a property by its name, e.g. `FAMILY_NAME`.
+    pub fn property(&self, name: &str) -> Result<PropertyValue, Error> {
+        for property in self.properties() {
+            let property = property?;
+            if property.name.as_bytes() == name.as_bytes() {
+                return Ok(property.value);
+            }
+        }
+        Err(Error::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+
+    use crate::{load_pcf_font, test_utils::FONT_LATIN1_LSBYTE};
+
+    use super::*;
+
+    /// Big endian, glyph row padded to int(4 bytes)
+    const FONT_VARIABLE: &[u8] =
+        include_bytes!("../test-fonts/fusion-pixel-12px-proportional-zh_hans-pad_to_int.pcf");
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_reading_properties() {
+        for data in [FONT_VARIABLE, FONT_LATIN1_LSBYTE] {
+            let font = load_pcf_font(Cursor::new(data)).unwrap();
+            let family_name = font.property("FAMILY_NAME").unwrap();
+            assert_eq!(
+                family_name.as_string().unwrap().as_str(),
+                Some("Fusion Pixel 12px Proportional zh_hans")
+            );
+            assert_eq!(font.property("PIXEL_SIZE").unwrap().as_integer(), Some(12));
+            assert_eq!(font.property("NO_SUCH_PROPERTY"), Err(Error::NotFound));
+
+            let first = font.properties().next().unwrap().unwrap();
+            assert_eq!(first.name.as_str(), Some("FOUNDRY"));
+            assert!(font.properties().all(|property| property.is_ok()));
+        }
+    }
+}
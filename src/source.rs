@@ -0,0 +1,322 @@
+//! Font data sources.
+//!
+//! Fonts are read on demand through [FontSource], so nothing but a few table locations is
+//! kept in memory. A font linked into the flash with `include_bytes!` is simply a `&[u8]`.
+//!
+//! Other storages only need to implement [FontSource::read_at]. `std` readers, like
+//! `std::fs::File`, are shared through a `RefCell` or a `Mutex`, and [OffsetSource] picks a font
+//! out of a larger storage.
+
+#[cfg(feature = "std")]
+use std::{
+    cell::RefCell,
+    io::{self, Read, Seek},
+    sync::{Mutex, PoisonError},
+};
+
+use crate::Error;
+
+/// Random access storage holding the PCF font data.
+pub trait FontSource {
+    /// Read exactly `buf.len()` bytes at the absolute `offset` into `buf`.
+    ///
+    /// Reading beyond the end of the data is an error.
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error>;
+
+    /// The whole font data, if it's in memory.
+    ///
+    /// This allows borrowing glyphs without copying, see [crate::PcfFont::glyph_slice].
+    #[inline]
+    fn as_bytes(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
+impl FontSource for [u8] {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let data = usize::try_from(offset)
+            .ok()
+            .and_then(|start| self.get(start..)?.get(..buf.len()))
+            .ok_or(Error::Io)?;
+        buf.copy_from_slice(data);
+        Ok(())
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+impl<S> FontSource for &S
+where
+    S: FontSource + ?Sized,
+{
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        (**self).read_at(offset, buf)
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> Option<&[u8]> {
+        (**self).as_bytes()
+    }
+}
+
+/// In-memory data wrapped in a cursor, the cursor position is ignored.
+#[cfg(feature = "std")]
+impl<T> FontSource for io::Cursor<T>
+where
+    T: AsRef<[u8]>,
+{
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        self.get_ref().as_ref().read_at(offset, buf)
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> Option<&[u8]> {
+        Some(self.get_ref().as_ref())
+    }
+}
+
+/// A readable & seekable object cloned for each read, so the reads never share a position.
+///
+/// Only use it for readers as cheap to clone as a cursor over borrowed data. Cloning a reader
+/// with its own buffer copies the buffer on every read, wrap those in a `RefCell` or a `Mutex`
+/// instead.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct IoSource<R>(pub R);
+
+#[cfg(feature = "std")]
+impl<R> FontSource for IoSource<R>
+where
+    R: io::Read + io::Seek + Clone,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        read_shared(&mut self.0.clone(), offset, buf)
+    }
+}
+
+/// Seek & read with a reader shared by all the reads.
+#[cfg(feature = "std")]
+fn read_shared<R>(reader: &mut R, offset: u64, buf: &mut [u8]) -> Result<(), Error>
+where
+    R: Read + Seek,
+{
+    reader.seek(io::SeekFrom::Start(offset))?;
+    reader.read_exact(buf)?;
+    Ok(())
+}
+
+/// A reader which can't be cloned, e.g. `std::fs::File`, only the table locations are kept in
+/// memory.
+///
+/// Reading glyphs while another read is in progress, like from a `Drop`, returns [Error::Other].
+#[cfg(feature = "std")]
+impl<R> FontSource for RefCell<R>
+where
+    R: Read + Seek,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let mut reader = self.try_borrow_mut().map_err(|_| Error::Other)?;
+        read_shared(&mut *reader, offset, buf)
+    }
+}
+
+/// Same with the [RefCell] one, but the font can be shared across threads.
+#[cfg(feature = "std")]
+impl<R> FontSource for Mutex<R>
+where
+    R: Read + Seek,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        // every read seeks first, so the reader is still usable after a panic
+        let mut reader = self.lock().unwrap_or_else(PoisonError::into_inner);
+        read_shared(&mut *reader, offset, buf)
+    }
+}
+
+/// A font stored at some offset of a larger storage, e.g. a flash partition or an asset bundle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffsetSource<S> {
+    source: S,
+    offset: u64,
+    length: u64,
+}
+
+impl<S> OffsetSource<S> {
+    /// The font takes `length` bytes at `offset` of `source`.
+    pub fn new(source: S, offset: u64, length: u64) -> Self {
+        Self {
+            source,
+            offset,
+            length,
+        }
+    }
+
+    /// Gets the underlying source back.
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S> FontSource for OffsetSource<S>
+where
+    S: FontSource,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let end = offset.checked_add(buf.len() as u64);
+        match (self.offset.checked_add(offset), end) {
+            (Some(start), Some(end)) if end <= self.length => self.source.read_at(start, buf),
+            _ => Err(Error::Io),
+        }
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        let start = usize::try_from(self.offset).ok()?;
+        let length = usize::try_from(self.length).ok()?;
+        self.source.as_bytes()?.get(start..)?.get(..length)
+    }
+}
+
+/// Sequential reader over a [FontSource], keeping the position like `io::Cursor`.
+pub(crate) struct SourceReader<'a, S: ?Sized> {
+    source: &'a S,
+    position: u64,
+}
+
+impl<'a, S> SourceReader<'a, S>
+where
+    S: FontSource + ?Sized,
+{
+    #[inline]
+    pub(crate) fn new(source: &'a S) -> Self {
+        Self {
+            source,
+            position: 0,
+        }
+    }
+
+    /// Move to the absolute `position`.
+    #[inline]
+    pub(crate) fn seek(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    /// Fill `buf` with the data at the current position, then move past it.
+    #[inline]
+    pub(crate) fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        self.source.read_at(self.position, buf)?;
+        self.position += buf.len() as u64;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{load_pcf_font, test_utils::FONT_LATIN1_LSBYTE};
+
+    use super::*;
+
+    #[test]
+    fn reading_slices() {
+        let data: &[u8] = &[0, 1, 2, 3, 4];
+        let mut buffer: [u8; 2] = [0; 2];
+        assert_eq!(data.read_at(1, &mut buffer), Ok(()));
+        assert_eq!(buffer, [1, 2]);
+        assert_eq!(data.read_at(3, &mut buffer), Ok(()));
+        assert_eq!(buffer, [3, 4]);
+        assert_eq!(data.read_at(4, &mut buffer), Err(Error::Io));
+        assert_eq!(data.read_at(u64::MAX, &mut buffer), Err(Error::Io));
+        assert_eq!(data.read_at(5, &mut []), Ok(()));
+
+        let mut reader = SourceReader::new(data);
+        reader.seek(2);
+        assert_eq!(reader.read_exact(&mut buffer), Ok(()));
+        assert_eq!(buffer, [2, 3]);
+        assert_eq!(reader.read_exact(&mut buffer), Err(Error::Io));
+    }
+
+    #[test]
+    fn reading_offset_sources() {
+        let data: &[u8] = &[0, 1, 2, 3, 4, 5];
+        let source = OffsetSource::new(data, 2, 3);
+        let mut buffer: [u8; 2] = [0; 2];
+        assert_eq!(source.read_at(0, &mut buffer), Ok(()));
+        assert_eq!(buffer, [2, 3]);
+        assert_eq!(source.read_at(1, &mut buffer), Ok(()));
+        assert_eq!(buffer, [3, 4]);
+        // never read beyond the window, even if the underlying data goes on
+        assert_eq!(source.read_at(2, &mut buffer), Err(Error::Io));
+        assert_eq!(source.read_at(u64::MAX, &mut buffer), Err(Error::Io));
+    }
+
+    #[test]
+    fn loading_from_offset_sources() {
+        let mut storage = [0xFF; 20000];
+        let length = FONT_LATIN1_LSBYTE.len();
+        storage[1000..1000 + length].copy_from_slice(FONT_LATIN1_LSBYTE);
+        let source = OffsetSource::new(&storage[..], 1000, length as u64);
+        let font = load_pcf_font(source).unwrap();
+        let expected = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let mut buffer: [u8; 32] = [0; 32];
+        let mut expected_buffer: [u8; 32] = [0; 32];
+        assert_eq!(
+            font.read_glyph_raw('A' as u32, &mut buffer),
+            expected.read_glyph_raw('A' as u32, &mut expected_buffer)
+        );
+        assert_eq!(buffer, expected_buffer);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_reading_io_sources() {
+        let source = IoSource(io::Cursor::new([0, 1, 2, 3]));
+        let mut buffer: [u8; 2] = [0; 2];
+        assert_eq!(source.read_at(2, &mut buffer), Ok(()));
+        assert_eq!(buffer, [2, 3]);
+        assert_eq!(source.read_at(3, &mut buffer), Err(Error::Io));
+
+        let font = load_pcf_font(IoSource(io::Cursor::new(FONT_LATIN1_LSBYTE))).unwrap();
+        assert_eq!(font.glyph_index('A' as u32), Ok(34));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_reading_files() {
+        use std::fs::File;
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test-fonts/fusion-pixel-12px-proportional-latin1-lsbyte.pcf"
+        );
+        let expected = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let mut expected_buffer: [u8; 32] = [0; 32];
+        expected
+            .read_glyph_raw('A' as u32, &mut expected_buffer)
+            .unwrap();
+
+        let mut buffer: [u8; 32] = [0; 32];
+        let font = load_pcf_font(RefCell::new(File::open(path).unwrap())).unwrap();
+        font.read_glyph_raw('A' as u32, &mut buffer).unwrap();
+        assert_eq!(buffer, expected_buffer);
+        // reentrant reads are refused instead of panicking
+        let _guard = font.source.borrow_mut();
+        assert_eq!(
+            font.read_glyph_raw('A' as u32, &mut buffer),
+            Err(Error::Other)
+        );
+
+        let font = load_pcf_font(Mutex::new(File::open(path).unwrap())).unwrap();
+        buffer.fill(0);
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| font.read_glyph_raw('A' as u32, &mut buffer).unwrap())
+                .join()
+                .unwrap();
+        });
+        assert_eq!(buffer, expected_buffer);
+    }
+}
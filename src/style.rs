@@ -1,8 +1,11 @@
+use core::convert::Infallible;
+
 use az::SaturatingAs as _;
 use embedded_graphics::{
     image::{Image, ImageRaw},
+    mono_font::{MonoFont, MonoTextStyle},
     pixelcolor::BinaryColor,
-    prelude::{DrawTarget, Drawable as _, PixelColor, Point, Size},
+    prelude::{Dimensions, DrawTarget, Drawable as _, Pixel, PixelColor, Point, Size},
     primitives::Rectangle,
     text::{
         renderer::{CharacterStyle, TextMetrics, TextRenderer},
@@ -12,26 +15,104 @@ use embedded_graphics::{
 
 use crate::{
     draw_target::{Background, Both, Foreground, MonoFontDrawTarget},
-    parser::MetricsEntry,
-    Error, PcfFont,
+    missing_glyph::{hex_box, hex_box_metrics, HEX_BOX_BYTES},
+    parser::{BoundingBox, MetricsEntry},
+    Error, FontSource, GlyphCache, MissingGlyph, PcfFont,
 };
 
-#[cfg(feature = "std")]
-use std::io;
+/// Glyphs are read into a buffer of this size when drawing, a tile of rows at a time.
+const GLYPH_BUFFER_SIZE: usize = 64;
+
+/// How [PcfFontStyle] computes the bounding box in [TextRenderer::measure_string].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MeasureMode {
+    /// The box covers the font's bounding box height and the characters' advance widths.
+    #[default]
+    FontBounds,
+    /// The box tightly covers the lit pixels, using the glyphs' ink metrics.
+    ///
+    /// The box is empty if no pixel is lit. The next position is not affected.
+    InkBounds,
+}
+
+/// A glyph of a font, by code point or by the internal glyph index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GlyphId {
+    CodePoint(u32),
+    Index(u16),
+}
+
+impl GlyphId {
+    fn metrics<T: FontSource>(self, font: &PcfFont<T>) -> Result<MetricsEntry, Error> {
+        match self {
+            Self::CodePoint(code_point) => font.get_glyph_metrics(code_point),
+            Self::Index(glyph_index) => font.get_glyph_metrics_by_index(glyph_index),
+        }
+    }
+
+    fn ink_metrics<T: FontSource>(self, font: &PcfFont<T>) -> Result<MetricsEntry, Error> {
+        match self {
+            Self::CodePoint(code_point) => font.get_glyph_ink_metrics(code_point),
+            Self::Index(glyph_index) => font.get_glyph_ink_metrics_by_index(glyph_index),
+        }
+    }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+    fn slice<T: FontSource>(self, font: &PcfFont<T>) -> Result<(&[u8], MetricsEntry), Error> {
+        match self {
+            Self::CodePoint(code_point) => font.glyph_slice(code_point),
+            Self::Index(glyph_index) => font.glyph_slice_by_index(glyph_index),
+        }
+    }
+
+    fn index<T: FontSource>(self, font: &PcfFont<T>) -> Result<u16, Error> {
+        match self {
+            Self::CodePoint(code_point) => font.glyph_index(code_point),
+            Self::Index(glyph_index) => Ok(glyph_index),
+        }
+    }
+}
+
+/// What's drawn for the characters missing from all the fonts, see [MissingGlyph].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Replacement {
+    /// The glyph of the font at the index, see [PcfFontStyle::font_at]
+    Glyph(usize, GlyphId),
+    HexBox,
+    Nothing,
+}
+
+#[derive(Debug, PartialEq)]
 #[non_exhaustive]
 pub struct PcfFontStyle<'a, T, C> {
     pub text_color: Option<C>,
     pub background_color: Option<C>,
     pub underline_color: DecorationColor<C>,
     pub strikethrough_color: DecorationColor<C>,
+    pub measure_mode: MeasureMode,
     pub font: &'a PcfFont<T>,
+    /// Fonts tried in order for the characters missing from [PcfFontStyle::font].
+    pub fallback_fonts: &'a [&'a PcfFont<T>],
+    /// Font drawing the characters missing from all the PCF fonts, so
+    /// [PcfFontStyle::missing_glyph] never applies.
+    pub mono_fallback: Option<&'a MonoFont<'a>>,
+    /// What's drawn for the characters missing from all the fonts.
+    pub missing_glyph: MissingGlyph,
+    /// Glyphs drawn are kept here, so redraws don't read the font again.
+    pub glyph_cache: Option<&'a GlyphCache<'a>>,
 }
 
+// the font is borrowed, no need for the source to be `Clone`
+impl<T, C: Copy> Clone for PcfFontStyle<'_, T, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, C: Copy> Copy for PcfFontStyle<'_, T, C> {}
+
 impl<'a, T, C> PcfFontStyle<'a, T, C>
 where
-    T: io::Read + io::Seek + Clone,
+    T: FontSource,
     C: PixelColor,
 {
     /// Initialize a PcfFontStyle, default all transparent/disabled
@@ -41,7 +122,12 @@ where
             background_color: None,
             underline_color: DecorationColor::None,
             strikethrough_color: DecorationColor::None,
+            measure_mode: MeasureMode::FontBounds,
             font,
+            fallback_fonts: &[],
+            mono_fallback: None,
+            missing_glyph: MissingGlyph::DefaultChar,
+            glyph_cache: None,
         }
     }
 
@@ -52,24 +138,238 @@ where
             && self.strikethrough_color.is_none()
     }
 
+    /// The font followed by the fallback fonts.
+    fn fonts(&self) -> impl Iterator<Item = &'a PcfFont<T>> {
+        core::iter::once(self.font).chain(self.fallback_fonts.iter().copied())
+    }
+
+    /// The font at the index of [PcfFontStyle::fonts], which also tells the fonts apart in the
+    /// glyph cache.
+    fn font_at(&self, index: usize) -> &'a PcfFont<T> {
+        match index {
+            0 => self.font,
+            _ => self.fallback_fonts[index - 1],
+        }
+    }
+
+    /// The bounding box covering all the fonts, so they share a baseline and a line height.
+    ///
+    /// The height saturates, for fonts with extreme ascents and descents.
+    fn bounding_box(&self) -> BoundingBox {
+        let union = |bounds: BoundingBox, other: BoundingBox| {
+            let max_ascent = bounds.max_ascent.max(other.max_ascent);
+            let max_descent = bounds.max_descent.min(other.max_descent);
+            BoundingBox {
+                width: bounds.width.max(other.width),
+                height: (i32::from(max_ascent) - i32::from(max_descent)).saturating_as(),
+                min_left_bearing: bounds.min_left_bearing.min(other.min_left_bearing),
+                max_right_bearing: bounds.max_right_bearing.max(other.max_right_bearing),
+                max_ascent,
+                max_descent,
+            }
+        };
+        let bounds = self
+            .fallback_fonts
+            .iter()
+            .map(|font| font.bounding_box)
+            .fold(self.font.bounding_box, union);
+        match self.mono_fallback.map(mono_font_metrics) {
+            Some(metrics) => union(
+                bounds,
+                BoundingBox {
+                    width: metrics.character_width,
+                    height: (i32::from(metrics.character_ascent)
+                        + i32::from(metrics.character_descent))
+                    .saturating_as(),
+                    min_left_bearing: 0,
+                    max_right_bearing: metrics.right_side_bearing,
+                    max_ascent: metrics.character_ascent,
+                    max_descent: -metrics.character_descent,
+                },
+            ),
+            None => bounds,
+        }
+    }
+
     /// the the glyphs drawing offset based on current baseline configuration.
     fn baseline_offset(&self, baseline: Baseline) -> i32 {
         // The `1`s to add are required to use lower edge as the alphabetic baseline,
         // matching other fonts behavior.
+        let bounding_box = self.bounding_box();
         match baseline {
             // Bounding box top pixel coincide with position pixel
-            Baseline::Top => self.font.bounding_box.max_ascent as i32,
+            Baseline::Top => bounding_box.max_ascent as i32,
             // Bounding box bottom pixel coincide with position pixel
-            Baseline::Bottom => (1 + self.font.bounding_box.max_descent) as i32,
+            Baseline::Bottom => 1 + i32::from(bounding_box.max_descent),
             // The bottom edge of the position pixel split the bounding box to 2 halves, and the lower half may be bigger
             Baseline::Middle => {
-                (1 + self.font.bounding_box.height / 2 + self.font.bounding_box.max_descent) as i32
+                1 + i32::from(bounding_box.height) / 2 + i32::from(bounding_box.max_descent)
             }
             // position pixel's lower edge coincide with font's baseline
             Baseline::Alphabetic => 1,
         }
     }
 
+    /// Gets the glyph's metrics from the first font having it, or from the glyph cache.
+    fn glyph_metrics(&self, code_point: u32) -> Result<MetricsEntry, Error> {
+        self.find_metrics(|index, font| {
+            let cache = self.glyph_cache;
+            match cache.and_then(|cache| cache.metrics(index, code_point)) {
+                Some(metrics) => Ok(metrics),
+                None => font.get_glyph_metrics(code_point),
+            }
+        })
+    }
+
+    /// Calls `f` with the index and each font, until one doesn't return [Error::NotFound].
+    ///
+    /// The whole character cell of the [PcfFontStyle::mono_fallback] font is used as the metrics
+    /// of the glyphs missing from all the fonts.
+    fn find_metrics(
+        &self,
+        f: impl FnMut(usize, &'a PcfFont<T>) -> Result<MetricsEntry, Error>,
+    ) -> Result<MetricsEntry, Error> {
+        match (self.find_in_fonts(f), self.mono_fallback) {
+            (Err(Error::NotFound), Some(mono_font)) => Ok(mono_font_metrics(mono_font)),
+            (result, _) => result,
+        }
+    }
+
+    /// Same with [PcfFontStyle::find_metrics], without the mono font.
+    fn find_in_fonts(
+        &self,
+        mut f: impl FnMut(usize, &'a PcfFont<T>) -> Result<MetricsEntry, Error>,
+    ) -> Result<MetricsEntry, Error> {
+        let mut result = Err(Error::NotFound);
+        for (index, font) in self.fonts().enumerate() {
+            result = f(index, font);
+            if !matches!(result, Err(Error::NotFound)) {
+                return result;
+            }
+        }
+        result
+    }
+
+    /// Resolve [PcfFontStyle::missing_glyph] on first use, as looking up `.notdef` scans the glyph
+    /// names.
+    fn replacement(&self, resolved: &mut Option<Replacement>) -> Replacement {
+        *resolved.get_or_insert_with(|| {
+            let glyph = match self.missing_glyph {
+                MissingGlyph::DefaultChar => self
+                    .font
+                    .default_glyph_index()
+                    .map(|glyph_index| (0, GlyphId::Index(glyph_index))),
+                MissingGlyph::Notdef => self
+                    .font
+                    .glyph_index_by_name(".notdef")
+                    .ok()
+                    .map(|glyph_index| (0, GlyphId::Index(glyph_index))),
+                MissingGlyph::Substitute(c) => self
+                    .fonts()
+                    .position(|font| font.glyph_index(c as u32).is_ok())
+                    .map(|index| (index, GlyphId::CodePoint(c as u32))),
+                MissingGlyph::HexBox => return Replacement::HexBox,
+                MissingGlyph::Skip => None,
+            };
+            match glyph {
+                Some((index, glyph)) => Replacement::Glyph(index, glyph),
+                None => Replacement::Nothing,
+            }
+        })
+    }
+
+    /// Metrics of the character as drawn, `None` if it takes no space.
+    fn char_metrics(
+        &self,
+        code_point: u32,
+        replacement: &mut Option<Replacement>,
+    ) -> Option<MetricsEntry> {
+        match self.glyph_metrics(code_point) {
+            Ok(metrics) => Some(metrics),
+            Err(Error::NotFound) => match self.replacement(replacement) {
+                Replacement::Glyph(index, glyph) => glyph.metrics(self.font_at(index)).ok(),
+                Replacement::HexBox => {
+                    Some(hex_box_metrics(code_point, self.bounding_box().max_ascent))
+                }
+                Replacement::Nothing => None,
+            },
+            // drawing skips these as well
+            Err(_) => None,
+        }
+    }
+
+    /// Ink metrics of the character as drawn, see [PcfFontStyle::char_metrics].
+    fn char_ink_metrics(
+        &self,
+        code_point: u32,
+        replacement: &mut Option<Replacement>,
+    ) -> Option<MetricsEntry> {
+        match self.find_in_fonts(|_, font| font.get_glyph_ink_metrics(code_point)) {
+            Ok(metrics) => Some(metrics),
+            // drawn by the mono font, see draw_string_binary
+            Err(Error::NotFound) if self.mono_fallback.is_some() => self
+                .mono_fallback
+                .zip(char::from_u32(code_point))
+                .map(|(mono_font, c)| mono_char_ink_metrics(mono_font, c)),
+            Err(Error::NotFound) => match self.replacement(replacement) {
+                Replacement::Glyph(index, glyph) => glyph.ink_metrics(self.font_at(index)).ok(),
+                // the border is lit all around
+                Replacement::HexBox => {
+                    Some(hex_box_metrics(code_point, self.bounding_box().max_ascent))
+                }
+                Replacement::Nothing => None,
+            },
+            Err(_) => None,
+        }
+    }
+
+    /// Sum of the characters' advance widths.
+    fn text_width(&self, text: &str) -> u32 {
+        let mut replacement = None;
+        text.chars()
+            .filter_map(|c| self.char_metrics(c as u32, &mut replacement))
+            .map(|metrics| metrics.character_width.max(0) as u32)
+            .sum()
+    }
+
+    /// The box covering the lit pixels of the text, the baseline is the upper edge of `position`.
+    fn measure_ink_bounds(&self, text: &str, position: Point) -> Rectangle {
+        let mut x = position.x;
+        // (top left, bottom right), both exclusive on the bottom right side
+        let mut bounds: Option<(Point, Point)> = None;
+        let mut replacement = None;
+        for c in text.chars() {
+            let Some(metrics) = self.char_metrics(c as u32, &mut replacement) else {
+                continue;
+            };
+            if let Some(ink) = self.char_ink_metrics(c as u32, &mut replacement) {
+                if ink.glyph_width() > 0 && ink.glyph_height() > 0 {
+                    let top_left = Point::new(
+                        x + ink.left_side_bearing as i32,
+                        position.y - ink.character_ascent as i32,
+                    );
+                    let bottom_right = Point::new(
+                        x + ink.right_side_bearing as i32,
+                        position.y + ink.character_descent as i32,
+                    );
+                    bounds = Some(match bounds {
+                        Some((tl, br)) => {
+                            (tl.component_min(top_left), br.component_max(bottom_right))
+                        }
+                        None => (top_left, bottom_right),
+                    });
+                }
+            }
+            x += metrics.character_width as i32;
+        }
+        match bounds {
+            Some((top_left, bottom_right)) => {
+                Rectangle::with_corners(top_left, bottom_right - Point::new(1, 1))
+            }
+            None => Rectangle::new(position, Size::zero()),
+        }
+    }
+
     fn draw_decorations<D>(
         &self,
         width: u32,
@@ -79,12 +379,6 @@ where
     where
         D: DrawTarget<Color = C>,
     {
-        let _ = width;
-        let _ = position;
-        let _ = target;
-        // TODO: draw strike through
-        // TODO: draw underline
-
         // strike through
         if let Some(color) = match self.strikethrough_color {
             DecorationColor::None => None,
@@ -124,46 +418,240 @@ where
         D: DrawTarget<Color = BinaryColor>,
     {
         // TODO: add a switch to control prefilling behavior, some monospaced fonts *may* work without this
-        let offset = Point::new(0, -self.font.bounding_box.max_ascent as i32);
+        let bounding_box = self.bounding_box();
+        let offset = Point::new(0, -bounding_box.max_ascent as i32);
         target.fill_solid(
             &Rectangle::new(
                 position + offset,
-                Size::new(width, self.font.bounding_box.height as u32),
+                Size::new(width, bounding_box.height as u32),
             ),
             BinaryColor::Off,
         )
     }
 
-    /// draw a single character at given position.
+    /// draw a tile of the glyph, `width` pixels wide at `tile_offset` in the glyph, at given
+    /// position.
     #[inline]
-    fn draw_single_char_binary<D>(
+    fn draw_glyph_tile_binary<D>(
         &self,
         glyph_data: &[u8],
+        width: u32,
         metrics: MetricsEntry,
+        tile_offset: Point,
         position: Point,
         target: &mut D,
     ) -> Result<(), D::Error>
     where
         D: DrawTarget<Color = BinaryColor>,
     {
-        // for all visible & invisible character
-        self.draw_prefill_binary(metrics.character_width as u32, position, target)?;
-
         // draw glyph only if it has data
-        if glyph_data.len() > 0 {
+        if !glyph_data.is_empty() {
             // map a glyph and paint it
-            let glyph = ImageRaw::<BinaryColor>::new(glyph_data, metrics.glyph_width() as u32);
+            let glyph = ImageRaw::<BinaryColor>::new(glyph_data, width);
             // per-glyph offset
             let offset = Point::new(
                 metrics.left_side_bearing as i32,
-                (-metrics.character_ascent) as i32,
+                -metrics.character_ascent as i32,
             );
-            Image::new(&glyph, position + offset).draw(target)?;
+            Image::new(&glyph, position + offset + tile_offset).draw(target)?;
         }
 
         Ok(())
     }
 
+    /// draw a whole glyph at given position, including the background.
+    #[inline]
+    fn draw_glyph_binary<D>(
+        &self,
+        glyph_data: &[u8],
+        metrics: MetricsEntry,
+        position: Point,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        // for all visible & invisible character
+        self.draw_prefill_binary(metrics.character_width.max(0) as u32, position, target)?;
+        let width = metrics.glyph_width() as u32;
+        self.draw_glyph_tile_binary(glyph_data, width, metrics, Point::zero(), position, target)
+    }
+
+    /// draw a single character at given position, returns the glyph's metrics if it's drawn.
+    ///
+    /// Glyphs are borrowed from the font data or the glyph cache if possible. Otherwise they are
+    /// read and drawn in tiles, so any glyph size works with a small buffer.
+    fn draw_single_char_binary<D>(
+        &self,
+        font_index: usize,
+        glyph: GlyphId,
+        position: Point,
+        target: &mut D,
+    ) -> Result<Result<MetricsEntry, Error>, D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let font = self.font_at(font_index);
+        match glyph.slice(font) {
+            Ok((data, metrics)) => {
+                self.draw_glyph_binary(data, metrics, position, target)?;
+                return Ok(Ok(metrics));
+            }
+            Err(Error::UnsupportedFormat) => {}
+            Err(error) => return Ok(Err(error)),
+        }
+
+        if let (Some(cache), GlyphId::CodePoint(code_point)) = (self.glyph_cache, glyph) {
+            let drawn = cache.with_glyph(font, font_index, code_point, |data, metrics| {
+                self.draw_glyph_binary(data, metrics, position, target)
+                    .map(|_| metrics)
+            });
+            match drawn {
+                Ok(drawn) => return drawn.map(Ok),
+                // too big for the cache, or the cache is in use
+                Err(Error::BufferTooSmall | Error::Other) => {}
+                Err(error) => return Ok(Err(error)),
+            }
+        }
+
+        let glyph_index = match glyph.index(font) {
+            Ok(glyph_index) => glyph_index,
+            Err(error) => return Ok(Err(error)),
+        };
+        let mut buf: [u8; GLYPH_BUFFER_SIZE] = [0; GLYPH_BUFFER_SIZE];
+        self.draw_glyph_tiles_binary(font, glyph_index, position, target, &mut buf)
+    }
+
+    /// draw a whole glyph at given position, read a tile at a time into `buf`.
+    ///
+    /// Tiles are as many rows as fit in `buf`, of the whole rows or the part fitting in `buf`. The
+    /// metrics and the location of the glyph are read once for all the tiles.
+    fn draw_glyph_tiles_binary<D>(
+        &self,
+        font: &PcfFont<T>,
+        glyph_index: u16,
+        position: Point,
+        target: &mut D,
+        buf: &mut [u8],
+    ) -> Result<Result<MetricsEntry, Error>, D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let bitmap = match font.glyph_bitmap_by_index(glyph_index) {
+            Ok(bitmap) => bitmap,
+            Err(error) => return Ok(Err(error)),
+        };
+        let (mut length, mut tile_row_bytes) = match font.read_glyph_tile(bitmap, 0, 0, buf) {
+            Ok(tile) => tile,
+            Err(error) => return Ok(Err(error)),
+        };
+        let (_, _, metrics) = bitmap;
+        self.draw_prefill_binary(metrics.character_width.max(0) as u32, position, target)?;
+
+        let glyph_width = metrics.glyph_width().max(0) as usize;
+        let glyph_height = metrics.glyph_height().max(0) as usize;
+        let row_bytes = glyph_width.div_ceil(8);
+        let (mut first_row, mut first_byte) = (0, 0);
+        loop {
+            // the last tile of the rows is clipped to the glyph width
+            let width = (glyph_width - first_byte * 8).min(tile_row_bytes * 8);
+            let tile_offset = Point::new(first_byte as i32 * 8, first_row as i32);
+            let tile = &buf[..length];
+            self.draw_glyph_tile_binary(
+                tile,
+                width as u32,
+                metrics,
+                tile_offset,
+                position,
+                target,
+            )?;
+
+            first_row += length.checked_div(tile_row_bytes).unwrap_or(glyph_height);
+            if first_row >= glyph_height {
+                first_row = 0;
+                first_byte += tile_row_bytes;
+            }
+            if first_byte >= row_bytes {
+                break;
+            }
+            match font.read_glyph_tile(bitmap, first_row, first_byte, buf) {
+                Ok((tile_length, tile_bytes)) if tile_length > 0 => {
+                    (length, tile_row_bytes) = (tile_length, tile_bytes);
+                }
+                _ => break,
+            }
+        }
+        Ok(Ok(metrics))
+    }
+
+    /// draw a single character with the first font having it.
+    fn draw_char_binary<D>(
+        &self,
+        code_point: u32,
+        position: Point,
+        target: &mut D,
+    ) -> Result<Result<MetricsEntry, Error>, D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let mut drawn = Err(Error::NotFound);
+        for index in 0..=self.fallback_fonts.len() {
+            let glyph = GlyphId::CodePoint(code_point);
+            drawn = self.draw_single_char_binary(index, glyph, position, target)?;
+            if !matches!(drawn, Err(Error::NotFound)) {
+                break;
+            }
+        }
+        Ok(drawn)
+    }
+
+    /// draw the replacement of a character missing from all the fonts.
+    fn draw_missing_char_binary<D>(
+        &self,
+        code_point: u32,
+        replacement: Replacement,
+        position: Point,
+        target: &mut D,
+    ) -> Result<Result<MetricsEntry, Error>, D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        match replacement {
+            Replacement::Glyph(index, glyph) => {
+                self.draw_single_char_binary(index, glyph, position, target)
+            }
+            Replacement::HexBox => {
+                let mut buf = [0; HEX_BOX_BYTES];
+                let max_ascent = self.bounding_box().max_ascent;
+                let (length, metrics) = hex_box(code_point, max_ascent, &mut buf);
+                self.draw_glyph_binary(&buf[..length], metrics, position, target)?;
+                Ok(Ok(metrics))
+            }
+            Replacement::Nothing => Ok(Err(Error::NotFound)),
+        }
+    }
+
+    /// draw a character of the mono font, the cell is aligned on the baseline.
+    fn draw_mono_char_binary<D>(
+        &self,
+        mono_font: &MonoFont<'_>,
+        c: char,
+        position: Point,
+        target: &mut D,
+    ) -> Result<MetricsEntry, D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let metrics = mono_font_metrics(mono_font);
+        self.draw_prefill_binary(metrics.character_width as u32, position, target)?;
+        let top_left = position - Point::new(0, metrics.character_ascent as i32);
+        // with both colors the cell is drawn with `fill_contiguous`, like the PCF glyphs
+        let mut style = MonoTextStyle::new(mono_font, BinaryColor::On);
+        style.background_color = Some(BinaryColor::Off);
+        style.draw_string(c.encode_utf8(&mut [0; 4]), top_left, Baseline::Top, target)?;
+        Ok(metrics)
+    }
+
     /// Draw the string, binary color, alphabetic baseline is the upper edge of the given pixel/location.
     ///
     /// Be careful that embedded-graphics actually uses the lower edge of
@@ -183,33 +671,21 @@ where
         character_ascent(absolute value) from the Y-Axis while drawing each character.
         */
 
-        // this buffer should be sufficient for glyphs size below 16*16
-        // TODO: adapt STD
-        let mut buf: [u8; 40] = [0; 40];
+        let mut replacement = None;
         for c in text.chars() {
-            match self.font.read_glyph_raw(c as u16, &mut buf) {
-                Ok((length, metrics)) => {
-                    self.draw_single_char_binary(&buf[..length], metrics, position, &mut target)?;
-                    position.x += metrics.character_width as i32;
-                }
-                Err(Error::NotFound) => {
-                    // look for the default character to use
-                    // TODO: add a switch to check default font
-                    match self.font.read_glyph_raw(self.font.default_char, &mut buf) {
-                        Ok((length, metrics)) => {
-                            self.draw_single_char_binary(
-                                &buf[..length],
-                                metrics,
-                                position,
-                                &mut target,
-                            )?;
-                            position.x += metrics.character_width as i32;
-                        }
-                        _ => { /* Just ignore the rest, assuming those are 0-width */ }
-                    }
-                }
-                _ => { /* Just ignore the rest, assuming those are 0-width */ }
-            };
+            let mut drawn = self.draw_char_binary(c as u32, position, &mut target)?;
+            if let (Err(Error::NotFound), Some(mono_font)) = (drawn, self.mono_fallback) {
+                drawn = Ok(self.draw_mono_char_binary(mono_font, c, position, &mut target)?);
+            }
+            if let Err(Error::NotFound) = drawn {
+                let replacement = self.replacement(&mut replacement);
+                drawn =
+                    self.draw_missing_char_binary(c as u32, replacement, position, &mut target)?;
+            }
+            // Just ignore the rest, assuming those are 0-width
+            if let Ok(metrics) = drawn {
+                position.x += metrics.character_width as i32;
+            }
         }
         Ok(position)
     }
@@ -218,7 +694,7 @@ where
 impl<T, C> TextRenderer for PcfFontStyle<'_, T, C>
 where
     C: PixelColor,
-    T: io::Read + io::Seek + Clone,
+    T: FontSource,
 {
     type Color = C;
 
@@ -251,18 +727,7 @@ where
                 position,
                 MonoFontDrawTarget::new(target, Background(background_color)),
             )?,
-            (None, None) => {
-                let default_width = self.font.bounding_box.width as u32;
-                let dx = text
-                    .chars()
-                    .map(|c| match self.font.get_glyph_metrics(c as u16) {
-                        Ok(metrics) => metrics.character_width as u32,
-                        Err(_) => default_width,
-                    })
-                    .sum();
-
-                position + Size::new(dx, 0)
-            }
+            (None, None) => position + Size::new(self.text_width(text), 0),
         };
 
         if next.x > position.x {
@@ -285,15 +750,12 @@ where
         D: DrawTarget<Color = Self::Color>,
     {
         if width != 0 {
-            let max_ascent =
-                (self.font.bounding_box.height + self.font.bounding_box.max_descent) as i32;
+            let bounding_box = self.bounding_box();
+            let max_ascent = i32::from(bounding_box.height) + i32::from(bounding_box.max_descent);
             position.y += self.baseline_offset(baseline) - max_ascent;
             if let Some(background_color) = self.background_color {
                 target.fill_solid(
-                    &Rectangle::new(
-                        position,
-                        Size::new(width, self.font.bounding_box.height as u32),
-                    ),
+                    &Rectangle::new(position, Size::new(width, bounding_box.height as u32)),
                     background_color,
                 )?;
             }
@@ -320,34 +782,35 @@ where
                 0,
                 self.baseline_offset(baseline) - self.baseline_offset(Baseline::Top),
             );
-        let default_width = self.font.bounding_box.width as u32;
-        let bb_width = text
-            .chars()
-            .map(|c| match self.font.get_glyph_metrics(c as u16) {
-                Ok(metrics) => metrics.character_width as u32,
-                Err(_) => default_width,
-            })
-            .sum();
+        let bb_width = self.text_width(text);
 
         // current decoration(underline etc.) implementation doesn't affect height
-        let bb_height = self.font.bounding_box.height as u32;
+        let bb_height = self.bounding_box().height as u32;
         let bb_size = Size::new(bb_width, bb_height);
 
+        let bounding_box = match self.measure_mode {
+            MeasureMode::FontBounds => Rectangle::new(bb_position, bb_size),
+            MeasureMode::InkBounds => self.measure_ink_bounds(
+                text,
+                position + Point::new(0, self.baseline_offset(baseline)),
+            ),
+        };
+
         TextMetrics {
-            bounding_box: Rectangle::new(bb_position, bb_size),
+            bounding_box,
             next_position: position + bb_size.x_axis(),
         }
     }
 
     fn line_height(&self) -> u32 {
-        self.font.bounding_box.height as u32
+        self.bounding_box().height as u32
     }
 }
 
 impl<T, C> CharacterStyle for PcfFontStyle<'_, T, C>
 where
     C: PixelColor,
-    T: io::Read + io::Seek + Clone,
+    T: FontSource,
 {
     type Color = C;
 
@@ -368,15 +831,106 @@ where
     }
 }
 
+/// Metrics of a glyph covering the whole character cell of the mono font.
+fn mono_font_metrics(mono_font: &MonoFont<'_>) -> MetricsEntry {
+    let size = mono_font.character_size;
+    // the baseline is the row of the lowest pixel above it
+    let ascent = mono_font.baseline.saturating_add(1).min(size.height);
+    MetricsEntry {
+        left_side_bearing: 0,
+        right_side_bearing: size.width.saturating_as(),
+        character_width: size
+            .width
+            .saturating_add(mono_font.character_spacing)
+            .saturating_as(),
+        character_ascent: ascent.saturating_as(),
+        character_descent: (size.height - ascent).saturating_as(),
+        character_attributes: 0,
+    }
+}
+
+/// Ink metrics of the character of the mono font, from the pixels it lights.
+fn mono_char_ink_metrics(mono_font: &MonoFont<'_>, c: char) -> MetricsEntry {
+    let metrics = mono_font_metrics(mono_font);
+    let mut lit = LitPixels(None);
+    let top_left = Point::new(0, -(metrics.character_ascent as i32));
+    let style = MonoTextStyle::new(mono_font, BinaryColor::On);
+    let Ok(_) = style.draw_string(
+        c.encode_utf8(&mut [0; 4]),
+        top_left,
+        Baseline::Top,
+        &mut lit,
+    );
+    match lit.0 {
+        // the baseline is the upper edge of row 0
+        Some((top_left, bottom_right)) => MetricsEntry {
+            left_side_bearing: top_left.x.saturating_as(),
+            right_side_bearing: (bottom_right.x + 1).saturating_as(),
+            character_ascent: (-top_left.y).saturating_as(),
+            character_descent: (bottom_right.y + 1).saturating_as(),
+            ..metrics
+        },
+        // nothing is lit, like the blank PCF glyphs
+        None => MetricsEntry {
+            left_side_bearing: 0,
+            right_side_bearing: 0,
+            character_ascent: 0,
+            character_descent: 0,
+            ..metrics
+        },
+    }
+}
+
+/// Draw target keeping only the bounds of the lit pixels, `(top left, bottom right)` inclusive.
+struct LitPixels(Option<(Point, Point)>);
+
+impl Dimensions for LitPixels {
+    fn bounding_box(&self) -> Rectangle {
+        // anything a mono font cell could cover
+        Rectangle::new(
+            Point::new(i16::MIN.into(), i16::MIN.into()),
+            Size::new(u16::MAX.into(), u16::MAX.into()),
+        )
+    }
+}
+
+impl DrawTarget for LitPixels {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if color.is_on() {
+                self.0 = Some(match self.0 {
+                    Some((tl, br)) => (tl.component_min(point), br.component_max(point)),
+                    None => (point, point),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Text style builder for PCF fonts.
 ///
 /// Mostly copied from embedded_graphics/mono_font/mono_text_style.rs to maintain
 /// API consistency.
-#[derive(Copy, Clone, Debug)]
+#[derive(Debug)]
 pub struct PcfFontStyleBuilder<'a, T, C> {
     style: PcfFontStyle<'a, T, C>,
 }
 
+impl<T, C: Copy> Clone for PcfFontStyleBuilder<'_, T, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, C: Copy> Copy for PcfFontStyleBuilder<'_, T, C> {}
+
 impl<'a, T, C> PcfFontStyleBuilder<'a, T, C>
 where
     C: PixelColor,
@@ -391,7 +945,12 @@ where
                 background_color: None,
                 underline_color: DecorationColor::None,
                 strikethrough_color: DecorationColor::None,
+                measure_mode: MeasureMode::FontBounds,
                 font,
+                fallback_fonts: &[],
+                mono_fallback: None,
+                missing_glyph: MissingGlyph::DefaultChar,
+                glyph_cache: None,
             },
         }
     }
@@ -466,8 +1025,437 @@ where
         self
     }
 
+    /// Sets how the bounding box is measured.
+    pub const fn measure_mode(mut self, measure_mode: MeasureMode) -> Self {
+        self.style.measure_mode = measure_mode;
+
+        self
+    }
+
+    /// Tries these fonts in order for the characters missing from the font.
+    ///
+    /// All the fonts are aligned on a shared baseline, and the line height covers all of them.
+    /// Fonts read from different kinds of storage can be mixed as `PcfFont<&dyn FontSource>`.
+    /// A [GlyphCache] tells the fonts apart by their position, the main font being the first.
+    pub const fn fallback_fonts(mut self, fallback_fonts: &'a [&'a PcfFont<T>]) -> Self {
+        self.style.fallback_fonts = fallback_fonts;
+
+        self
+    }
+
+    /// Draws the characters missing from all the PCF fonts with the mono font, e.g. `FONT_6X10`.
+    ///
+    /// The mono font is aligned on the PCF fonts' baseline.
+    pub const fn mono_fallback(mut self, mono_fallback: &'a MonoFont<'a>) -> Self {
+        self.style.mono_fallback = Some(mono_fallback);
+
+        self
+    }
+
+    /// Sets what's drawn for the characters missing from all the fonts, see [MissingGlyph].
+    pub const fn missing_glyph(mut self, missing_glyph: MissingGlyph) -> Self {
+        self.style.missing_glyph = missing_glyph;
+
+        self
+    }
+
+    /// Keeps the glyphs drawn in the cache, see [GlyphCache].
+    pub const fn glyph_cache(mut self, glyph_cache: &'a GlyphCache<'a>) -> Self {
+        self.style.glyph_cache = Some(glyph_cache);
+
+        self
+    }
+
     /// Builds the text style.
     pub const fn build(self) -> PcfFontStyle<'a, T, C> {
         self.style
     }
 }
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{BinaryColor, Rgb565},
+    };
+
+    use crate::{load_pcf_font, test_utils::FONT_LATIN1_LSBYTE};
+
+    use super::*;
+
+    /// Big endian, glyph row padded to byte, metrics cover the whole character cell
+    const FONT_CELL_METRICS: &[u8] =
+        include_bytes!("../test-fonts/fusion-pixel-12px-proportional-zh_hans.pcf");
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_measuring_ink_bounds() {
+        let font = load_pcf_font(Cursor::new(FONT_CELL_METRICS)).unwrap();
+        let font_style = PcfFontStyleBuilder::<_, Rgb565>::new(&font).build();
+        let ink_style = PcfFontStyleBuilder::<_, Rgb565>::new(&font)
+            .measure_mode(MeasureMode::InkBounds)
+            .build();
+
+        let font_metrics = font_style.measure_string(" A", Point::zero(), Baseline::Alphabetic);
+        let ink_metrics = ink_style.measure_string(" A", Point::zero(), Baseline::Alphabetic);
+        assert_eq!(ink_metrics.next_position, font_metrics.next_position);
+        // 'A' is 7x9 pixels standing on the baseline, after a 6 pixels wide space
+        assert_eq!(
+            ink_metrics.bounding_box,
+            Rectangle::new(Point::new(6, -8), Size::new(7, 9))
+        );
+
+        let blank = ink_style.measure_string("  ", Point::new(3, 4), Baseline::Top);
+        assert_eq!(blank.bounding_box.size, Size::zero());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_drawing_borrowed_and_copied_glyphs() {
+        use std::cell::RefCell;
+
+        // glyphs are borrowed from the slice, but copied from the reader
+        let borrowed = load_pcf_font(FONT_CELL_METRICS).unwrap();
+        let copied = load_pcf_font(RefCell::new(Cursor::new(FONT_CELL_METRICS))).unwrap();
+        let mut expected = MockDisplay::new();
+        let mut display = MockDisplay::new();
+        let text = "Aé世界";
+        PcfFontStyleBuilder::new(&borrowed)
+            .text_color(BinaryColor::On)
+            .build()
+            .draw_string(text, Point::zero(), Baseline::Top, &mut expected)
+            .unwrap();
+        PcfFontStyleBuilder::new(&copied)
+            .text_color(BinaryColor::On)
+            .build()
+            .draw_string(text, Point::zero(), Baseline::Top, &mut display)
+            .unwrap();
+        display.assert_eq(&expected);
+        assert_ne!(display, MockDisplay::new());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_drawing_cached_glyphs() {
+        use std::cell::RefCell;
+
+        use crate::GlyphCacheSlot;
+
+        let font = load_pcf_font(RefCell::new(Cursor::new(FONT_CELL_METRICS))).unwrap();
+        // Latin glyphs fit in the slots, CJK ones don't
+        let mut slots = [GlyphCacheSlot::EMPTY; 4];
+        let mut data = [0; 4 * 20];
+        let cache = GlyphCache::new(&mut slots, &mut data);
+        let text = "Ab世b";
+        let mut expected = MockDisplay::new();
+        PcfFontStyleBuilder::new(&font)
+            .text_color(BinaryColor::On)
+            .build()
+            .draw_string(text, Point::zero(), Baseline::Top, &mut expected)
+            .unwrap();
+        let style = PcfFontStyleBuilder::new(&font)
+            .text_color(BinaryColor::On)
+            .glyph_cache(&cache)
+            .build();
+        for _ in 0..2 {
+            let mut display = MockDisplay::new();
+            let next = style
+                .draw_string(text, Point::zero(), Baseline::Top, &mut display)
+                .unwrap();
+            display.assert_eq(&expected);
+            assert_eq!(
+                style.measure_string(text, Point::zero(), Baseline::Top),
+                font_style_metrics(&font, text)
+            );
+            assert_eq!(next, font_style_metrics(&font, text).next_position);
+        }
+        assert!(cache.metrics(0, 'b' as u32).is_some());
+        assert_eq!(cache.metrics(0, '世' as u32), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_drawing_code_points_beyond_bmp() {
+        let font = load_pcf_font(FONT_CELL_METRICS).unwrap();
+        let style = PcfFontStyleBuilder::new(&font)
+            .text_color(BinaryColor::On)
+            .build();
+        // U+1F600 is missing, not U+F600, and U+10041 is not 'A'
+        for text in ["\u{1F600}", "\u{10041}"] {
+            let mut display = MockDisplay::new();
+            let mut expected = MockDisplay::new();
+            let missing = char::from_u32(0xFFFF).unwrap().to_string();
+            style
+                .draw_string(text, Point::zero(), Baseline::Top, &mut display)
+                .unwrap();
+            style
+                .draw_string(&missing, Point::zero(), Baseline::Top, &mut expected)
+                .unwrap();
+            display.assert_eq(&expected);
+            assert_eq!(
+                style.measure_string(text, Point::zero(), Baseline::Top),
+                style.measure_string(&missing, Point::zero(), Baseline::Top)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_drawing_with_fallback_fonts() {
+        use std::cell::RefCell;
+
+        use crate::GlyphCacheSlot;
+
+        // the fallback font is read from another kind of source
+        let reader = RefCell::new(Cursor::new(FONT_CELL_METRICS));
+        let latin = load_pcf_font(&FONT_LATIN1_LSBYTE as &dyn FontSource).unwrap();
+        let cjk = load_pcf_font(&reader as &dyn FontSource).unwrap();
+        let fallback_fonts = [&cjk];
+        let style = PcfFontStyleBuilder::new(&latin)
+            .text_color(BinaryColor::On)
+            .fallback_fonts(&fallback_fonts)
+            .build();
+
+        // glyphs of both fonts stand on the same baseline
+        let position = Point::new(0, 20);
+        let mut display = MockDisplay::new();
+        let next = style
+            .draw_string("A世", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        let mut expected = MockDisplay::new();
+        let middle = PcfFontStyleBuilder::new(&latin)
+            .text_color(BinaryColor::On)
+            .build()
+            .draw_string("A", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        let expected_next = PcfFontStyleBuilder::new(&cjk)
+            .text_color(BinaryColor::On)
+            .build()
+            .draw_string("世", middle, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+        assert_eq!(next, expected_next);
+
+        // the glyphs of the fallback font are cached as its own
+        let mut slots = [GlyphCacheSlot::EMPTY; 2];
+        let mut data = [0; 2 * 32];
+        let cache = GlyphCache::new(&mut slots, &mut data);
+        let cached_style = PcfFontStyleBuilder::new(&latin)
+            .text_color(BinaryColor::On)
+            .fallback_fonts(&fallback_fonts)
+            .glyph_cache(&cache)
+            .build();
+        let mut display = MockDisplay::new();
+        cached_style
+            .draw_string("A世", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        display.assert_eq(&expected);
+        assert!(cache.metrics(1, '世' as u32).is_some());
+        assert_eq!(cache.metrics(0, '世' as u32), None);
+
+        let height = |font: &PcfFont<_>| font.bounding_box().height as u32;
+        assert_eq!(style.line_height(), height(&latin).max(height(&cjk)));
+        let metrics = style.measure_string("A世", position, Baseline::Top);
+        assert_eq!(metrics.next_position, expected_next);
+        assert_eq!(
+            metrics.bounding_box,
+            Rectangle::new(
+                position,
+                Size::new((next - position).x as u32, style.line_height())
+            )
+        );
+    }
+
+    #[test]
+    fn combining_extreme_bounding_boxes() {
+        let mut tall = load_pcf_font(FONT_CELL_METRICS).unwrap();
+        tall.bounding_box.max_ascent = i16::MAX;
+        let mut deep = load_pcf_font(FONT_CELL_METRICS).unwrap();
+        deep.bounding_box.max_descent = i16::MIN;
+        let fallback_fonts = [&deep];
+        let style = PcfFontStyleBuilder::<_, BinaryColor>::new(&tall)
+            .fallback_fonts(&fallback_fonts)
+            .build();
+        let bounding_box = style.bounding_box();
+        assert_eq!(
+            (bounding_box.max_ascent, bounding_box.max_descent),
+            (i16::MAX, i16::MIN)
+        );
+        assert_eq!(bounding_box.height, i16::MAX);
+        assert_eq!(style.baseline_offset(Baseline::Bottom), 1 + i16::MIN as i32);
+        assert_eq!(
+            style.baseline_offset(Baseline::Middle),
+            1 + i16::MAX as i32 / 2 + i16::MIN as i32
+        );
+    }
+
+    #[test]
+    fn drawing_glyphs_in_tiles() {
+        let font = load_pcf_font(FONT_CELL_METRICS).unwrap();
+        let style = PcfFontStyleBuilder::new(&font)
+            .text_color(BinaryColor::On)
+            .build();
+        for c in "Aé世".chars() {
+            let position = Point::new(0, 14);
+            // the background is prefilled, then the glyph drawn over it
+            let mut expected = MockDisplay::new();
+            expected.set_allow_overdraw(true);
+            let (data, metrics) = font.glyph_slice(c as u32).unwrap();
+            style
+                .draw_glyph_binary(data, metrics, position, &mut expected)
+                .unwrap();
+            // tiles narrower than the rows, and of several rows
+            let glyph_index = font.glyph_index(c as u32).unwrap();
+            for buffer in [&mut [0; 1][..], &mut [0; 3]] {
+                let mut display = MockDisplay::new();
+                display.set_allow_overdraw(true);
+                let drawn = style
+                    .draw_glyph_tiles_binary(&font, glyph_index, position, &mut display, buffer)
+                    .unwrap();
+                assert_eq!(drawn, Ok(metrics));
+                display.assert_eq(&expected);
+            }
+        }
+    }
+
+    #[test]
+    fn drawing_with_mono_fallback() {
+        use embedded_graphics::mono_font::iso_8859_2::FONT_6X10;
+
+        let font = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let style = PcfFontStyleBuilder::new(&font)
+            .text_color(BinaryColor::On)
+            .mono_fallback(&FONT_6X10)
+            .build();
+
+        // 'Ő' is missing from the Latin-1 font, it stands on the same baseline with 'A'
+        let position = Point::new(0, 20);
+        let mut display = MockDisplay::new();
+        let next = style
+            .draw_string("AŐ", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        let mut expected = MockDisplay::new();
+        let middle = PcfFontStyleBuilder::new(&font)
+            .text_color(BinaryColor::On)
+            .build()
+            .draw_string("A", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        let expected_next = MonoTextStyle::new(&FONT_6X10, BinaryColor::On)
+            .draw_string("Ő", middle, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+        assert_eq!(next, expected_next);
+        assert_eq!(
+            style
+                .measure_string("AŐ", position, Baseline::Alphabetic)
+                .next_position,
+            expected_next
+        );
+
+        // the ink is measured from the lit pixels of the mono glyph, not its cell
+        let ink_style = PcfFontStyleBuilder::new(&font)
+            .text_color(BinaryColor::On)
+            .mono_fallback(&FONT_6X10)
+            .measure_mode(MeasureMode::InkBounds)
+            .build();
+        let mut display = MockDisplay::new();
+        ink_style
+            .draw_string("Ő", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        assert_eq!(
+            ink_style
+                .measure_string("Ő", position, Baseline::Alphabetic)
+                .bounding_box,
+            display.affected_area()
+        );
+
+        // the line covers both fonts
+        let ascent = font.bounding_box().max_ascent.max(8) as u32;
+        let descent = (-font.bounding_box().max_descent).max(2) as u32;
+        assert_eq!(style.line_height(), ascent + descent);
+    }
+
+    #[test]
+    fn drawing_missing_glyphs() {
+        let font = load_pcf_font(FONT_LATIN1_LSBYTE).unwrap();
+        let draw = |missing_glyph: MissingGlyph, text: &str| {
+            let style = PcfFontStyleBuilder::new(&font)
+                .text_color(BinaryColor::On)
+                .missing_glyph(missing_glyph)
+                .build();
+            let mut display = MockDisplay::new();
+            let next = style
+                .draw_string(text, Point::zero(), Baseline::Top, &mut display)
+                .unwrap();
+            // measuring and drawing agree, with or without colors
+            assert_eq!(
+                style
+                    .measure_string(text, Point::zero(), Baseline::Top)
+                    .next_position,
+                next
+            );
+            let transparent = PcfFontStyle {
+                text_color: None,
+                ..style
+            };
+            assert_eq!(
+                transparent.draw_string(
+                    text,
+                    Point::zero(),
+                    Baseline::Top,
+                    &mut MockDisplay::new()
+                ),
+                Ok(next)
+            );
+            (display, next)
+        };
+
+        // U+4E16 is missing from the Latin-1 font
+        let text = "A\u{4E16}B";
+        let (display, next) = draw(MissingGlyph::Skip, text);
+        let (expected, expected_next) = draw(MissingGlyph::Skip, "AB");
+        display.assert_eq(&expected);
+        assert_eq!(next, expected_next);
+
+        let (display, next) = draw(MissingGlyph::Substitute('?'), text);
+        let (expected, expected_next) = draw(MissingGlyph::Skip, "A?B");
+        display.assert_eq(&expected);
+        assert_eq!(next, expected_next);
+
+        // `.notdef` is the first glyph of this font
+        let (display, next) = draw(MissingGlyph::Notdef, text);
+        let notdef = font.get_glyph_metrics_by_index(0).unwrap();
+        assert!(notdef.character_width > 0);
+        assert_eq!(
+            next.x,
+            draw(MissingGlyph::Skip, "AB").1.x + notdef.character_width as i32
+        );
+        assert_ne!(display, draw(MissingGlyph::Skip, "AB").0);
+
+        // whatever the default character is, it's measured as drawn
+        draw(MissingGlyph::DefaultChar, text);
+
+        let (display, next) = draw(MissingGlyph::HexBox, text);
+        assert_eq!(next.x, draw(MissingGlyph::Skip, "AB").1.x + 12);
+        assert_ne!(display, draw(MissingGlyph::Skip, "AB").0);
+        let ink_style = PcfFontStyleBuilder::<_, BinaryColor>::new(&font)
+            .missing_glyph(MissingGlyph::HexBox)
+            .measure_mode(MeasureMode::InkBounds)
+            .build();
+        assert_eq!(
+            ink_style
+                .measure_string("\u{4E16}", Point::zero(), Baseline::Alphabetic)
+                .bounding_box,
+            Rectangle::new(Point::new(0, -12), Size::new(11, 13))
+        );
+    }
+
+    fn font_style_metrics<T: FontSource>(font: &PcfFont<T>, text: &str) -> TextMetrics {
+        PcfFontStyleBuilder::<_, BinaryColor>::new(font)
+            .build()
+            .measure_string(text, Point::zero(), Baseline::Top)
+    }
+}
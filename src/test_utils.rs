@@ -0,0 +1,129 @@
+//! Fixtures shared by the tests of several modules.
+
+use core::cell::Cell;
+
+#[cfg(feature = "embedded-io")]
+use embedded_io::{ErrorKind, ErrorType, SeekFrom};
+#[cfg(all(feature = "embedded-io-async", not(feature = "embedded-io")))]
+use embedded_io_async::{ErrorKind, ErrorType, SeekFrom};
+
+use crate::{Error, FontSource};
+
+/// Latin-1 font, little endian, glyph row padded to byte, with ink metrics
+pub(crate) const FONT_LATIN1_LSBYTE: &[u8] =
+    include_bytes!("../test-fonts/fusion-pixel-12px-proportional-latin1-lsbyte.pcf");
+/// Latin-1 font, big endian, LSBit first, glyph row padded to short and stored in shorts
+pub(crate) const FONT_LATIN1_LSBIT_UNIT2: &[u8] = include_bytes!(
+    "../test-fonts/fusion-pixel-12px-proportional-latin1-lsbit-unit2-pad_to_short.pcf"
+);
+
+/// [FONT_LATIN1_LSBYTE] with a broken properties table, too small for its entries if
+/// `broken_table`, otherwise with every string of the entries out of the string pool.
+pub(crate) fn font_with_broken_properties(broken_table: bool) -> [u8; FONT_LATIN1_LSBYTE.len()] {
+    let mut data = [0; FONT_LATIN1_LSBYTE.len()];
+    data.copy_from_slice(FONT_LATIN1_LSBYTE);
+    // the TOC and this font are both little endian
+    let u32_at = |data: &[u8], offset: usize| {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+    };
+    let entry = (0..u32_at(&data, 4))
+        .map(|i| 8 + i * 16)
+        .find(|entry| u32_at(&data, *entry) == 1)
+        .unwrap();
+    if broken_table {
+        data[entry + 8..entry + 12].copy_from_slice(&8u32.to_le_bytes());
+    } else {
+        let table = u32_at(&data, entry + 12);
+        let entries_size = (u32_at(&data, table + 4) * 9).next_multiple_of(4);
+        let strings_size = table + 8 + entries_size;
+        data[strings_size..strings_size + 4].copy_from_slice(&1u32.to_le_bytes());
+    }
+    data
+}
+
+/// Source counting the reads, and not in memory as far as the font knows.
+pub(crate) struct CountingSource {
+    data: &'static [u8],
+    pub(crate) reads: Cell<usize>,
+}
+
+impl CountingSource {
+    pub(crate) fn new(data: &'static [u8]) -> Self {
+        Self {
+            data,
+            reads: Cell::new(0),
+        }
+    }
+}
+
+impl FontSource for CountingSource {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<(), Error> {
+        self.reads.set(self.reads.get() + 1);
+        self.data.read_at(offset, buf)
+    }
+}
+
+/// Reader giving at most 5 bytes each time, like a slow device.
+///
+/// Both the blocking and the async `embedded-io` traits are implemented, they share the error type.
+#[cfg(any(feature = "embedded-io", feature = "embedded-io-async"))]
+pub(crate) struct SlowReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+#[cfg(any(feature = "embedded-io", feature = "embedded-io-async"))]
+impl<'a> SlowReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn read_slowly(&mut self, buf: &mut [u8]) -> usize {
+        let data = self.data.get(self.position..).unwrap_or_default();
+        let count = buf.len().min(data.len()).min(5);
+        buf[..count].copy_from_slice(&data[..count]);
+        self.position += count;
+        count
+    }
+
+    fn seek_from_start(&mut self, pos: SeekFrom) -> Result<u64, ErrorKind> {
+        match pos {
+            SeekFrom::Start(offset) => self.position = offset as usize,
+            _ => return Err(ErrorKind::Unsupported),
+        }
+        Ok(self.position as u64)
+    }
+}
+
+#[cfg(any(feature = "embedded-io", feature = "embedded-io-async"))]
+impl ErrorType for SlowReader<'_> {
+    type Error = ErrorKind;
+}
+
+#[cfg(feature = "embedded-io")]
+impl embedded_io::Read for SlowReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.read_slowly(buf))
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl embedded_io::Seek for SlowReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+        self.seek_from_start(pos)
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl embedded_io_async::Read for SlowReader<'_> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.read_slowly(buf))
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl embedded_io_async::Seek for SlowReader<'_> {
+    async fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+        self.seek_from_start(pos)
+    }
+}
@@ -1,11 +1,52 @@
 #![allow(dead_code)]
 
+/// Byte order of multi-byte fields, each PCF table declares its own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ByteOrder {
+    /// Least significant byte first
+    LsbFirst,
+    /// Most significant byte first
+    MsbFirst,
+}
+
+impl ByteOrder {
+    /// convert bytes data to u32
+    #[inline]
+    pub(crate) fn u32_from_bytes_ref(self, buf: &[u8]) -> u32 {
+        match self {
+            Self::LsbFirst => u32_from_le_bytes_ref(buf),
+            Self::MsbFirst => u32_from_be_bytes_ref(buf),
+        }
+    }
+
+    /// convert bytes data to i32
+    #[inline]
+    pub(crate) fn i32_from_bytes_ref(self, buf: &[u8]) -> i32 {
+        self.u32_from_bytes_ref(buf) as i32
+    }
+
+    /// convert bytes data to u16
+    #[inline]
+    pub(crate) fn u16_from_bytes_ref(self, buf: &[u8]) -> u16 {
+        match self {
+            Self::LsbFirst => u16_from_le_bytes_ref(buf),
+            Self::MsbFirst => u16_from_be_bytes_ref(buf),
+        }
+    }
+
+    /// convert bytes data to i16
+    #[inline]
+    pub(crate) fn i16_from_bytes_ref(self, buf: &[u8]) -> i16 {
+        self.u16_from_bytes_ref(buf) as i16
+    }
+}
+
 /// convert bytes data to u32
 #[inline]
 pub(crate) fn u32_from_le_bytes_ref(buf: &[u8]) -> u32 {
     let mut value: u32 = 0;
-    for i in 0..4 {
-        value |= (buf[i] as u32) << (i * 8)
+    for (i, byte) in buf[..4].iter().enumerate() {
+        value |= (*byte as u32) << (i * 8)
     }
     value
 }
@@ -20,8 +61,8 @@ pub(crate) fn i32_from_le_bytes_ref(buf: &[u8]) -> i32 {
 #[inline]
 pub(crate) fn u32_from_be_bytes_ref(buf: &[u8]) -> u32 {
     let mut value: u32 = 0;
-    for i in 0..4 {
-        value |= (buf[i] as u32) << ((3 - i) * 8)
+    for (i, byte) in buf[..4].iter().enumerate() {
+        value |= (*byte as u32) << ((3 - i) * 8)
     }
     value
 }
@@ -75,4 +116,17 @@ mod test {
         let value2 = u32::from_be_bytes(bytes);
         assert_eq!(value1, value2);
     }
+
+    #[test]
+    fn converters_byte_order() {
+        let bytes: [u8; 4] = [0x00, 0x12, 0x75, 0x27];
+        assert_eq!(
+            ByteOrder::LsbFirst.u32_from_bytes_ref(&bytes),
+            u32::from_le_bytes(bytes)
+        );
+        assert_eq!(
+            ByteOrder::MsbFirst.i16_from_bytes_ref(&bytes[2..]),
+            i16::from_be_bytes([0x75, 0x27])
+        );
+    }
 }